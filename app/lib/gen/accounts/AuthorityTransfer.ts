@@ -0,0 +1,95 @@
+import { PublicKey, Connection } from "@solana/web3.js"
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface AuthorityTransferFields {
+  farm: PublicKey
+  newAuthority: PublicKey
+}
+
+export interface AuthorityTransferJSON {
+  farm: string
+  newAuthority: string
+}
+
+export class AuthorityTransfer {
+  readonly farm: PublicKey
+  readonly newAuthority: PublicKey
+
+  static readonly discriminator = Buffer.from([
+    43, 243, 199, 71, 139, 255, 231, 113,
+  ])
+
+  static readonly layout = borsh.struct([
+    borsh.publicKey("farm"),
+    borsh.publicKey("newAuthority"),
+  ])
+
+  constructor(fields: AuthorityTransferFields) {
+    this.farm = fields.farm
+    this.newAuthority = fields.newAuthority
+  }
+
+  static async fetch(
+    c: Connection,
+    address: PublicKey
+  ): Promise<AuthorityTransfer | null> {
+    const info = await c.getAccountInfo(address)
+
+    if (info === null) {
+      return null
+    }
+    if (!info.owner.equals(PROGRAM_ID)) {
+      throw new Error("account doesn't belong to this program")
+    }
+
+    return this.decode(info.data)
+  }
+
+  static async fetchMultiple(
+    c: Connection,
+    addresses: PublicKey[]
+  ): Promise<Array<AuthorityTransfer | null>> {
+    const infos = await c.getMultipleAccountsInfo(addresses)
+
+    return infos.map((info) => {
+      if (info === null) {
+        return null
+      }
+      if (!info.owner.equals(PROGRAM_ID)) {
+        throw new Error("account doesn't belong to this program")
+      }
+
+      return this.decode(info.data)
+    })
+  }
+
+  static decode(data: Buffer): AuthorityTransfer {
+    if (!data.slice(0, 8).equals(AuthorityTransfer.discriminator)) {
+      throw new Error("invalid account discriminator")
+    }
+
+    const dec = AuthorityTransfer.layout.decode(data.slice(8))
+
+    return new AuthorityTransfer({
+      farm: dec.farm,
+      newAuthority: dec.newAuthority,
+    })
+  }
+
+  toJSON(): AuthorityTransferJSON {
+    return {
+      farm: this.farm.toString(),
+      newAuthority: this.newAuthority.toString(),
+    }
+  }
+
+  static fromJSON(obj: AuthorityTransferJSON): AuthorityTransfer {
+    return new AuthorityTransfer({
+      farm: new PublicKey(obj.farm),
+      newAuthority: new PublicKey(obj.newAuthority),
+    })
+  }
+}
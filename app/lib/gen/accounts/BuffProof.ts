@@ -0,0 +1,103 @@
+import { PublicKey, Connection } from "@solana/web3.js"
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface BuffProofFields {
+  farm: PublicKey
+  mint: PublicKey
+  factor: BN
+}
+
+export interface BuffProofJSON {
+  farm: string
+  mint: string
+  factor: string
+}
+
+export class BuffProof {
+  readonly farm: PublicKey
+  readonly mint: PublicKey
+  readonly factor: BN
+
+  static readonly discriminator = Buffer.from([
+    9, 179, 174, 220, 6, 169, 182, 204,
+  ])
+
+  static readonly layout = borsh.struct([
+    borsh.publicKey("farm"),
+    borsh.publicKey("mint"),
+    borsh.u64("factor"),
+  ])
+
+  constructor(fields: BuffProofFields) {
+    this.farm = fields.farm
+    this.mint = fields.mint
+    this.factor = fields.factor
+  }
+
+  static async fetch(
+    c: Connection,
+    address: PublicKey
+  ): Promise<BuffProof | null> {
+    const info = await c.getAccountInfo(address)
+
+    if (info === null) {
+      return null
+    }
+    if (!info.owner.equals(PROGRAM_ID)) {
+      throw new Error("account doesn't belong to this program")
+    }
+
+    return this.decode(info.data)
+  }
+
+  static async fetchMultiple(
+    c: Connection,
+    addresses: PublicKey[]
+  ): Promise<Array<BuffProof | null>> {
+    const infos = await c.getMultipleAccountsInfo(addresses)
+
+    return infos.map((info) => {
+      if (info === null) {
+        return null
+      }
+      if (!info.owner.equals(PROGRAM_ID)) {
+        throw new Error("account doesn't belong to this program")
+      }
+
+      return this.decode(info.data)
+    })
+  }
+
+  static decode(data: Buffer): BuffProof {
+    if (!data.slice(0, 8).equals(BuffProof.discriminator)) {
+      throw new Error("invalid account discriminator")
+    }
+
+    const dec = BuffProof.layout.decode(data.slice(8))
+
+    return new BuffProof({
+      farm: dec.farm,
+      mint: dec.mint,
+      factor: dec.factor,
+    })
+  }
+
+  toJSON(): BuffProofJSON {
+    return {
+      farm: this.farm.toString(),
+      mint: this.mint.toString(),
+      factor: this.factor.toString(),
+    }
+  }
+
+  static fromJSON(obj: BuffProofJSON): BuffProof {
+    return new BuffProof({
+      farm: new PublicKey(obj.farm),
+      mint: new PublicKey(obj.mint),
+      factor: new BN(obj.factor),
+    })
+  }
+}
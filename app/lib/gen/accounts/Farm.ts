@@ -8,6 +8,14 @@ export interface FarmFields {
   authority: PublicKey
   reward: types.RewardFields
   bump: Array<number>
+  accrualPolicy: types.AccrualPolicyKind
+  rewardStartTs: BN
+  rewardEndTs: BN
+  seedAuthority: PublicKey
+  state: types.FarmStateKind
+  pausedTime: BN
+  activeStakes: number
+  stakesCounted: boolean
   reserved: Array<number>
 }
 
@@ -15,6 +23,14 @@ export interface FarmJSON {
   authority: string
   reward: types.RewardJSON
   bump: Array<number>
+  accrualPolicy: types.AccrualPolicyJSON
+  rewardStartTs: string
+  rewardEndTs: string
+  seedAuthority: string
+  state: types.FarmStateJSON
+  pausedTime: string
+  activeStakes: number
+  stakesCounted: boolean
   reserved: Array<number>
 }
 
@@ -22,6 +38,14 @@ export class Farm {
   readonly authority: PublicKey
   readonly reward: types.Reward
   readonly bump: Array<number>
+  readonly accrualPolicy: types.AccrualPolicyKind
+  readonly rewardStartTs: BN
+  readonly rewardEndTs: BN
+  readonly seedAuthority: PublicKey
+  readonly state: types.FarmStateKind
+  readonly pausedTime: BN
+  readonly activeStakes: number
+  readonly stakesCounted: boolean
   readonly reserved: Array<number>
 
   static readonly discriminator = Buffer.from([
@@ -32,13 +56,29 @@ export class Farm {
     borsh.publicKey("authority"),
     types.Reward.layout("reward"),
     borsh.array(borsh.u8(), 1, "bump"),
-    borsh.array(borsh.u8(), 64, "reserved"),
+    types.AccrualPolicy.layout("accrualPolicy"),
+    borsh.u64("rewardStartTs"),
+    borsh.u64("rewardEndTs"),
+    borsh.publicKey("seedAuthority"),
+    types.FarmState.layout("state"),
+    borsh.u64("pausedTime"),
+    borsh.u32("activeStakes"),
+    borsh.bool("stakesCounted"),
+    borsh.array(borsh.u8(), 1, "reserved"),
   ])
 
   constructor(fields: FarmFields) {
     this.authority = fields.authority
     this.reward = new types.Reward({ ...fields.reward })
     this.bump = fields.bump
+    this.accrualPolicy = fields.accrualPolicy
+    this.rewardStartTs = fields.rewardStartTs
+    this.rewardEndTs = fields.rewardEndTs
+    this.seedAuthority = fields.seedAuthority
+    this.state = fields.state
+    this.pausedTime = fields.pausedTime
+    this.activeStakes = fields.activeStakes
+    this.stakesCounted = fields.stakesCounted
     this.reserved = fields.reserved
   }
 
@@ -84,6 +124,14 @@ export class Farm {
       authority: dec.authority,
       reward: types.Reward.fromDecoded(dec.reward),
       bump: dec.bump,
+      accrualPolicy: types.AccrualPolicy.fromDecoded(dec.accrualPolicy),
+      rewardStartTs: dec.rewardStartTs,
+      rewardEndTs: dec.rewardEndTs,
+      seedAuthority: dec.seedAuthority,
+      state: types.FarmState.fromDecoded(dec.state),
+      pausedTime: dec.pausedTime,
+      activeStakes: dec.activeStakes,
+      stakesCounted: dec.stakesCounted,
       reserved: dec.reserved,
     })
   }
@@ -93,6 +141,14 @@ export class Farm {
       authority: this.authority.toString(),
       reward: this.reward.toJSON(),
       bump: this.bump,
+      accrualPolicy: this.accrualPolicy.toJSON(),
+      rewardStartTs: this.rewardStartTs.toString(),
+      rewardEndTs: this.rewardEndTs.toString(),
+      seedAuthority: this.seedAuthority.toString(),
+      state: this.state.toJSON(),
+      pausedTime: this.pausedTime.toString(),
+      activeStakes: this.activeStakes,
+      stakesCounted: this.stakesCounted,
       reserved: this.reserved,
     }
   }
@@ -102,6 +158,14 @@ export class Farm {
       authority: new PublicKey(obj.authority),
       reward: types.Reward.fromJSON(obj.reward),
       bump: obj.bump,
+      accrualPolicy: types.AccrualPolicy.fromJSON(obj.accrualPolicy),
+      rewardStartTs: new BN(obj.rewardStartTs),
+      rewardEndTs: new BN(obj.rewardEndTs),
+      seedAuthority: new PublicKey(obj.seedAuthority),
+      state: types.FarmState.fromJSON(obj.state),
+      pausedTime: new BN(obj.pausedTime),
+      activeStakes: obj.activeStakes,
+      stakesCounted: obj.stakesCounted,
       reserved: obj.reserved,
     })
   }
@@ -11,7 +11,12 @@ export interface FarmerFields {
   totalRewardRate: BN
   lastUpdate: BN
   bump: Array<number>
-  reserved: Array<number>
+  rewardShortfall: BN
+  secondaryAccruedRewards: BN
+  secondaryRewardRate: BN
+  pausedSeconds: BN
+  secondaryEarningRate: BN
+  reserved2: Array<number>
 }
 
 export interface FarmerJSON {
@@ -21,7 +26,12 @@ export interface FarmerJSON {
   totalRewardRate: string
   lastUpdate: string
   bump: Array<number>
-  reserved: Array<number>
+  rewardShortfall: string
+  secondaryAccruedRewards: string
+  secondaryRewardRate: string
+  pausedSeconds: string
+  secondaryEarningRate: string
+  reserved2: Array<number>
 }
 
 export class Farmer {
@@ -31,7 +41,12 @@ export class Farmer {
   readonly totalRewardRate: BN
   readonly lastUpdate: BN
   readonly bump: Array<number>
-  readonly reserved: Array<number>
+  readonly rewardShortfall: BN
+  readonly secondaryAccruedRewards: BN
+  readonly secondaryRewardRate: BN
+  readonly pausedSeconds: BN
+  readonly secondaryEarningRate: BN
+  readonly reserved2: Array<number>
 
   static readonly discriminator = Buffer.from([
     254, 63, 81, 98, 130, 38, 28, 219,
@@ -44,7 +59,12 @@ export class Farmer {
     borsh.u64("totalRewardRate"),
     borsh.u64("lastUpdate"),
     borsh.array(borsh.u8(), 1, "bump"),
-    borsh.array(borsh.u8(), 64, "reserved"),
+    borsh.u64("rewardShortfall"),
+    borsh.u64("secondaryAccruedRewards"),
+    borsh.u64("secondaryRewardRate"),
+    borsh.u64("pausedSeconds"),
+    borsh.u64("secondaryEarningRate"),
+    borsh.array(borsh.u8(), 24, "reserved2"),
   ])
 
   constructor(fields: FarmerFields) {
@@ -54,7 +74,12 @@ export class Farmer {
     this.totalRewardRate = fields.totalRewardRate
     this.lastUpdate = fields.lastUpdate
     this.bump = fields.bump
-    this.reserved = fields.reserved
+    this.rewardShortfall = fields.rewardShortfall
+    this.secondaryAccruedRewards = fields.secondaryAccruedRewards
+    this.secondaryRewardRate = fields.secondaryRewardRate
+    this.pausedSeconds = fields.pausedSeconds
+    this.secondaryEarningRate = fields.secondaryEarningRate
+    this.reserved2 = fields.reserved2
   }
 
   static async fetch(
@@ -105,7 +130,12 @@ export class Farmer {
       totalRewardRate: dec.totalRewardRate,
       lastUpdate: dec.lastUpdate,
       bump: dec.bump,
-      reserved: dec.reserved,
+      rewardShortfall: dec.rewardShortfall,
+      secondaryAccruedRewards: dec.secondaryAccruedRewards,
+      secondaryRewardRate: dec.secondaryRewardRate,
+      pausedSeconds: dec.pausedSeconds,
+      secondaryEarningRate: dec.secondaryEarningRate,
+      reserved2: dec.reserved2,
     })
   }
 
@@ -117,7 +147,12 @@ export class Farmer {
       totalRewardRate: this.totalRewardRate.toString(),
       lastUpdate: this.lastUpdate.toString(),
       bump: this.bump,
-      reserved: this.reserved,
+      rewardShortfall: this.rewardShortfall.toString(),
+      secondaryAccruedRewards: this.secondaryAccruedRewards.toString(),
+      secondaryRewardRate: this.secondaryRewardRate.toString(),
+      pausedSeconds: this.pausedSeconds.toString(),
+      secondaryEarningRate: this.secondaryEarningRate.toString(),
+      reserved2: this.reserved2,
     }
   }
 
@@ -129,7 +164,12 @@ export class Farmer {
       totalRewardRate: new BN(obj.totalRewardRate),
       lastUpdate: new BN(obj.lastUpdate),
       bump: obj.bump,
-      reserved: obj.reserved,
+      rewardShortfall: new BN(obj.rewardShortfall),
+      secondaryAccruedRewards: new BN(obj.secondaryAccruedRewards),
+      secondaryRewardRate: new BN(obj.secondaryRewardRate),
+      pausedSeconds: new BN(obj.pausedSeconds),
+      secondaryEarningRate: new BN(obj.secondaryEarningRate),
+      reserved2: obj.reserved2,
     })
   }
 }
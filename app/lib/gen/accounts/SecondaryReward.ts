@@ -0,0 +1,111 @@
+import { PublicKey, Connection } from "@solana/web3.js"
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface SecondaryRewardFields {
+  farm: PublicKey
+  reward: types.RewardFields
+  bump: Array<number>
+  earningRate: BN
+}
+
+export interface SecondaryRewardJSON {
+  farm: string
+  reward: types.RewardJSON
+  bump: Array<number>
+  earningRate: string
+}
+
+export class SecondaryReward {
+  readonly farm: PublicKey
+  readonly reward: types.Reward
+  readonly bump: Array<number>
+  readonly earningRate: BN
+
+  static readonly discriminator = Buffer.from([
+    183, 130, 112, 44, 104, 176, 103, 193,
+  ])
+
+  static readonly layout = borsh.struct([
+    borsh.publicKey("farm"),
+    types.Reward.layout("reward"),
+    borsh.array(borsh.u8(), 1, "bump"),
+    borsh.u64("earningRate"),
+  ])
+
+  constructor(fields: SecondaryRewardFields) {
+    this.farm = fields.farm
+    this.reward = new types.Reward({ ...fields.reward })
+    this.bump = fields.bump
+    this.earningRate = fields.earningRate
+  }
+
+  static async fetch(
+    c: Connection,
+    address: PublicKey
+  ): Promise<SecondaryReward | null> {
+    const info = await c.getAccountInfo(address)
+
+    if (info === null) {
+      return null
+    }
+    if (!info.owner.equals(PROGRAM_ID)) {
+      throw new Error("account doesn't belong to this program")
+    }
+
+    return this.decode(info.data)
+  }
+
+  static async fetchMultiple(
+    c: Connection,
+    addresses: PublicKey[]
+  ): Promise<Array<SecondaryReward | null>> {
+    const infos = await c.getMultipleAccountsInfo(addresses)
+
+    return infos.map((info) => {
+      if (info === null) {
+        return null
+      }
+      if (!info.owner.equals(PROGRAM_ID)) {
+        throw new Error("account doesn't belong to this program")
+      }
+
+      return this.decode(info.data)
+    })
+  }
+
+  static decode(data: Buffer): SecondaryReward {
+    if (!data.slice(0, 8).equals(SecondaryReward.discriminator)) {
+      throw new Error("invalid account discriminator")
+    }
+
+    const dec = SecondaryReward.layout.decode(data.slice(8))
+
+    return new SecondaryReward({
+      farm: dec.farm,
+      reward: types.Reward.fromDecoded(dec.reward),
+      bump: dec.bump,
+      earningRate: dec.earningRate,
+    })
+  }
+
+  toJSON(): SecondaryRewardJSON {
+    return {
+      farm: this.farm.toString(),
+      reward: this.reward.toJSON(),
+      bump: this.bump,
+      earningRate: this.earningRate.toString(),
+    }
+  }
+
+  static fromJSON(obj: SecondaryRewardJSON): SecondaryReward {
+    return new SecondaryReward({
+      farm: new PublicKey(obj.farm),
+      reward: types.Reward.fromJSON(obj.reward),
+      bump: obj.bump,
+      earningRate: new BN(obj.earningRate),
+    })
+  }
+}
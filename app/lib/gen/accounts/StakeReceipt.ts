@@ -11,7 +11,12 @@ export interface StakeReceiptFields {
   endTs: BN | null
   amount: BN
   rewardRate: BN
-  reserved: Array<number>
+  lockEndTs: BN
+  unstakeCooldown: number
+  cooldownEndTs: BN
+  rateEpoch: number
+  secondaryRewardRate: BN
+  whitelist: PublicKey
 }
 
 export interface StakeReceiptJSON {
@@ -21,7 +26,12 @@ export interface StakeReceiptJSON {
   endTs: string | null
   amount: string
   rewardRate: string
-  reserved: Array<number>
+  lockEndTs: string
+  unstakeCooldown: number
+  cooldownEndTs: string
+  rateEpoch: number
+  secondaryRewardRate: string
+  whitelist: string
 }
 
 export class StakeReceipt {
@@ -31,7 +41,12 @@ export class StakeReceipt {
   readonly endTs: BN | null
   readonly amount: BN
   readonly rewardRate: BN
-  readonly reserved: Array<number>
+  readonly lockEndTs: BN
+  readonly unstakeCooldown: number
+  readonly cooldownEndTs: BN
+  readonly rateEpoch: number
+  readonly secondaryRewardRate: BN
+  readonly whitelist: PublicKey
 
   static readonly discriminator = Buffer.from([
     189, 110, 129, 87, 79, 225, 96, 177,
@@ -44,7 +59,12 @@ export class StakeReceipt {
     borsh.option(borsh.u64(), "endTs"),
     borsh.u64("amount"),
     borsh.u64("rewardRate"),
-    borsh.array(borsh.u8(), 64, "reserved"),
+    borsh.u64("lockEndTs"),
+    borsh.u32("unstakeCooldown"),
+    borsh.u64("cooldownEndTs"),
+    borsh.u32("rateEpoch"),
+    borsh.u64("secondaryRewardRate"),
+    borsh.publicKey("whitelist"),
   ])
 
   constructor(fields: StakeReceiptFields) {
@@ -54,7 +74,12 @@ export class StakeReceipt {
     this.endTs = fields.endTs
     this.amount = fields.amount
     this.rewardRate = fields.rewardRate
-    this.reserved = fields.reserved
+    this.lockEndTs = fields.lockEndTs
+    this.unstakeCooldown = fields.unstakeCooldown
+    this.cooldownEndTs = fields.cooldownEndTs
+    this.rateEpoch = fields.rateEpoch
+    this.secondaryRewardRate = fields.secondaryRewardRate
+    this.whitelist = fields.whitelist
   }
 
   static async fetch(
@@ -105,7 +130,12 @@ export class StakeReceipt {
       endTs: dec.endTs,
       amount: dec.amount,
       rewardRate: dec.rewardRate,
-      reserved: dec.reserved,
+      lockEndTs: dec.lockEndTs,
+      unstakeCooldown: dec.unstakeCooldown,
+      cooldownEndTs: dec.cooldownEndTs,
+      rateEpoch: dec.rateEpoch,
+      secondaryRewardRate: dec.secondaryRewardRate,
+      whitelist: dec.whitelist,
     })
   }
 
@@ -117,7 +147,12 @@ export class StakeReceipt {
       endTs: (this.endTs && this.endTs.toString()) || null,
       amount: this.amount.toString(),
       rewardRate: this.rewardRate.toString(),
-      reserved: this.reserved,
+      lockEndTs: this.lockEndTs.toString(),
+      unstakeCooldown: this.unstakeCooldown,
+      cooldownEndTs: this.cooldownEndTs.toString(),
+      rateEpoch: this.rateEpoch,
+      secondaryRewardRate: this.secondaryRewardRate.toString(),
+      whitelist: this.whitelist.toString(),
     }
   }
 
@@ -129,7 +164,12 @@ export class StakeReceipt {
       endTs: (obj.endTs && new BN(obj.endTs)) || null,
       amount: new BN(obj.amount),
       rewardRate: new BN(obj.rewardRate),
-      reserved: obj.reserved,
+      lockEndTs: new BN(obj.lockEndTs),
+      unstakeCooldown: obj.unstakeCooldown,
+      cooldownEndTs: new BN(obj.cooldownEndTs),
+      rateEpoch: obj.rateEpoch,
+      secondaryRewardRate: new BN(obj.secondaryRewardRate),
+      whitelist: new PublicKey(obj.whitelist),
     })
   }
 }
@@ -9,6 +9,12 @@ export interface WhitelistProofFields {
   farm: PublicKey
   rewardRate: BN
   ty: types.WhitelistTypeKind
+  minStakeDuration: BN
+  unstakeCooldown: number
+  rateEpoch: number
+  secondaryRewardRate: BN
+  reserved: Array<number>
+  reserved2: Array<number>
 }
 
 export interface WhitelistProofJSON {
@@ -16,6 +22,12 @@ export interface WhitelistProofJSON {
   farm: string
   rewardRate: string
   ty: types.WhitelistTypeJSON
+  minStakeDuration: string
+  unstakeCooldown: number
+  rateEpoch: number
+  secondaryRewardRate: string
+  reserved: Array<number>
+  reserved2: Array<number>
 }
 
 export class WhitelistProof {
@@ -23,6 +35,12 @@ export class WhitelistProof {
   readonly farm: PublicKey
   readonly rewardRate: BN
   readonly ty: types.WhitelistTypeKind
+  readonly minStakeDuration: BN
+  readonly unstakeCooldown: number
+  readonly rateEpoch: number
+  readonly secondaryRewardRate: BN
+  readonly reserved: Array<number>
+  readonly reserved2: Array<number>
 
   static readonly discriminator = Buffer.from([
     194, 230, 60, 10, 60, 98, 236, 39,
@@ -33,6 +51,12 @@ export class WhitelistProof {
     borsh.publicKey("farm"),
     borsh.u64("rewardRate"),
     types.WhitelistType.layout("ty"),
+    borsh.u64("minStakeDuration"),
+    borsh.u32("unstakeCooldown"),
+    borsh.u32("rateEpoch"),
+    borsh.u64("secondaryRewardRate"),
+    borsh.array(borsh.u8(), 24, "reserved"),
+    borsh.array(borsh.u8(), 32, "reserved2"),
   ])
 
   constructor(fields: WhitelistProofFields) {
@@ -40,6 +64,12 @@ export class WhitelistProof {
     this.farm = fields.farm
     this.rewardRate = fields.rewardRate
     this.ty = fields.ty
+    this.minStakeDuration = fields.minStakeDuration
+    this.unstakeCooldown = fields.unstakeCooldown
+    this.rateEpoch = fields.rateEpoch
+    this.secondaryRewardRate = fields.secondaryRewardRate
+    this.reserved = fields.reserved
+    this.reserved2 = fields.reserved2
   }
 
   static async fetch(
@@ -88,6 +118,12 @@ export class WhitelistProof {
       farm: dec.farm,
       rewardRate: dec.rewardRate,
       ty: types.WhitelistType.fromDecoded(dec.ty),
+      minStakeDuration: dec.minStakeDuration,
+      unstakeCooldown: dec.unstakeCooldown,
+      rateEpoch: dec.rateEpoch,
+      secondaryRewardRate: dec.secondaryRewardRate,
+      reserved: dec.reserved,
+      reserved2: dec.reserved2,
     })
   }
 
@@ -97,6 +133,12 @@ export class WhitelistProof {
       farm: this.farm.toString(),
       rewardRate: this.rewardRate.toString(),
       ty: this.ty.toJSON(),
+      minStakeDuration: this.minStakeDuration.toString(),
+      unstakeCooldown: this.unstakeCooldown,
+      rateEpoch: this.rateEpoch,
+      secondaryRewardRate: this.secondaryRewardRate.toString(),
+      reserved: this.reserved,
+      reserved2: this.reserved2,
     }
   }
 
@@ -106,6 +148,12 @@ export class WhitelistProof {
       farm: new PublicKey(obj.farm),
       rewardRate: new BN(obj.rewardRate),
       ty: types.WhitelistType.fromJSON(obj.ty),
+      minStakeDuration: new BN(obj.minStakeDuration),
+      unstakeCooldown: obj.unstakeCooldown,
+      rateEpoch: obj.rateEpoch,
+      secondaryRewardRate: new BN(obj.secondaryRewardRate),
+      reserved: obj.reserved,
+      reserved2: obj.reserved2,
     })
   }
 }
@@ -1,9 +1,21 @@
+export { AuthorityTransfer } from "./AuthorityTransfer"
+export type {
+  AuthorityTransferFields,
+  AuthorityTransferJSON,
+} from "./AuthorityTransfer"
+export { BuffProof } from "./BuffProof"
+export type { BuffProofFields, BuffProofJSON } from "./BuffProof"
 export { FarmManager } from "./FarmManager"
 export type { FarmManagerFields, FarmManagerJSON } from "./FarmManager"
 export { Farmer } from "./Farmer"
 export type { FarmerFields, FarmerJSON } from "./Farmer"
 export { Farm } from "./Farm"
 export type { FarmFields, FarmJSON } from "./Farm"
+export { SecondaryReward } from "./SecondaryReward"
+export type {
+  SecondaryRewardFields,
+  SecondaryRewardJSON,
+} from "./SecondaryReward"
 export { StakeReceipt } from "./StakeReceipt"
 export type { StakeReceiptFields, StakeReceiptJSON } from "./StakeReceipt"
 export { WhitelistProof } from "./WhitelistProof"
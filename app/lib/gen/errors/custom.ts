@@ -8,6 +8,26 @@ export type CustomError =
   | InvalidWhitelistType
   | FactorMustBeGtZero
   | ArithmeticError
+  | CouldNotWithdrawReward
+  | InvalidMerkleProof
+  | InvalidRewardWindow
+  | MissingPermission
+  | InvalidPermissions
+  | InvalidSecondaryReward
+  | InvalidSecondaryRewardAccounts
+  | InvalidUnstakeAmount
+  | UnsupportedTokenStandard
+  | InvalidBatchAccounts
+  | FarmerNotEmpty
+  | StakingPaused
+  | FarmFrozen
+  | FarmNotEmpty
+  | AccountAlreadyMigrated
+  | WhitelistMismatch
+  | StakeUpToDate
+  | StakesNotCounted
+  | SecondaryRewardOwed
+  | FarmNotClosed
 
 export class CooldownIsNotOver extends Error {
   static readonly code = 6000
@@ -108,6 +128,232 @@ export class ArithmeticError extends Error {
   }
 }
 
+export class CouldNotWithdrawReward extends Error {
+  static readonly code = 6009
+  readonly code = 6009
+  readonly name = "CouldNotWithdrawReward"
+  readonly msg = "Insufficient available reward. Could not withdraw."
+
+  constructor(readonly logs?: string[]) {
+    super("6009: Insufficient available reward. Could not withdraw.")
+  }
+}
+
+export class InvalidMerkleProof extends Error {
+  static readonly code = 6010
+  readonly code = 6010
+  readonly name = "InvalidMerkleProof"
+  readonly msg = "Mint is not part of the whitelisted merkle tree."
+
+  constructor(readonly logs?: string[]) {
+    super("6010: Mint is not part of the whitelisted merkle tree.")
+  }
+}
+
+export class InvalidRewardWindow extends Error {
+  static readonly code = 6011
+  readonly code = 6011
+  readonly name = "InvalidRewardWindow"
+  readonly msg = "Invalid reward window."
+
+  constructor(readonly logs?: string[]) {
+    super("6011: Invalid reward window.")
+  }
+}
+
+export class MissingPermission extends Error {
+  static readonly code = 6012
+  readonly code = 6012
+  readonly name = "MissingPermission"
+  readonly msg = "Farm manager does not have the required permissions."
+
+  constructor(readonly logs?: string[]) {
+    super("6012: Farm manager does not have the required permissions.")
+  }
+}
+
+export class InvalidPermissions extends Error {
+  static readonly code = 6013
+  readonly code = 6013
+  readonly name = "InvalidPermissions"
+  readonly msg = "Invalid farm manager permissions."
+
+  constructor(readonly logs?: string[]) {
+    super("6013: Invalid farm manager permissions.")
+  }
+}
+
+export class InvalidSecondaryReward extends Error {
+  static readonly code = 6014
+  readonly code = 6014
+  readonly name = "InvalidSecondaryReward"
+  readonly msg =
+    "The secondary reward must be a different token than the farm reward."
+
+  constructor(readonly logs?: string[]) {
+    super(
+      "6014: The secondary reward must be a different token than the farm reward."
+    )
+  }
+}
+
+export class InvalidSecondaryRewardAccounts extends Error {
+  static readonly code = 6015
+  readonly code = 6015
+  readonly name = "InvalidSecondaryRewardAccounts"
+  readonly msg = "Invalid secondary reward accounts."
+
+  constructor(readonly logs?: string[]) {
+    super("6015: Invalid secondary reward accounts.")
+  }
+}
+
+export class InvalidUnstakeAmount extends Error {
+  static readonly code = 6016
+  readonly code = 6016
+  readonly name = "InvalidUnstakeAmount"
+  readonly msg = "Partial unstakes must leave some tokens staked."
+
+  constructor(readonly logs?: string[]) {
+    super("6016: Partial unstakes must leave some tokens staked.")
+  }
+}
+
+export class UnsupportedTokenStandard extends Error {
+  static readonly code = 6017
+  readonly code = 6017
+  readonly name = "UnsupportedTokenStandard"
+  readonly msg = "Only SPL Token mints and non-programmable NFTs can be staked."
+
+  constructor(readonly logs?: string[]) {
+    super("6017: Only SPL Token mints and non-programmable NFTs can be staked.")
+  }
+}
+
+export class InvalidBatchAccounts extends Error {
+  static readonly code = 6018
+  readonly code = 6018
+  readonly name = "InvalidBatchAccounts"
+  readonly msg = "Invalid accounts for the batch of gems."
+
+  constructor(readonly logs?: string[]) {
+    super("6018: Invalid accounts for the batch of gems.")
+  }
+}
+
+export class FarmerNotEmpty extends Error {
+  static readonly code = 6019
+  readonly code = 6019
+  readonly name = "FarmerNotEmpty"
+  readonly msg = "Farmer still has staked gems or unclaimed rewards."
+
+  constructor(readonly logs?: string[]) {
+    super("6019: Farmer still has staked gems or unclaimed rewards.")
+  }
+}
+
+export class StakingPaused extends Error {
+  static readonly code = 6020
+  readonly code = 6020
+  readonly name = "StakingPaused"
+  readonly msg = "Staking is paused on this farm."
+
+  constructor(readonly logs?: string[]) {
+    super("6020: Staking is paused on this farm.")
+  }
+}
+
+export class FarmFrozen extends Error {
+  static readonly code = 6021
+  readonly code = 6021
+  readonly name = "FarmFrozen"
+  readonly msg = "Farm is frozen."
+
+  constructor(readonly logs?: string[]) {
+    super("6021: Farm is frozen.")
+  }
+}
+
+export class FarmNotEmpty extends Error {
+  static readonly code = 6022
+  readonly code = 6022
+  readonly name = "FarmNotEmpty"
+  readonly msg =
+    "Farm still has active stakes, reserved rewards or a secondary reward."
+
+  constructor(readonly logs?: string[]) {
+    super(
+      "6022: Farm still has active stakes, reserved rewards or a secondary reward."
+    )
+  }
+}
+
+export class AccountAlreadyMigrated extends Error {
+  static readonly code = 6023
+  readonly code = 6023
+  readonly name = "AccountAlreadyMigrated"
+  readonly msg = "Account already uses the current layout."
+
+  constructor(readonly logs?: string[]) {
+    super("6023: Account already uses the current layout.")
+  }
+}
+
+export class WhitelistMismatch extends Error {
+  static readonly code = 6024
+  readonly code = 6024
+  readonly name = "WhitelistMismatch"
+  readonly msg = "Stake was made through another whitelist."
+
+  constructor(readonly logs?: string[]) {
+    super("6024: Stake was made through another whitelist.")
+  }
+}
+
+export class StakeUpToDate extends Error {
+  static readonly code = 6025
+  readonly code = 6025
+  readonly name = "StakeUpToDate"
+  readonly msg = "Stake already uses the whitelist's current rates."
+
+  constructor(readonly logs?: string[]) {
+    super("6025: Stake already uses the whitelist's current rates.")
+  }
+}
+
+export class StakesNotCounted extends Error {
+  static readonly code = 6026
+  readonly code = 6026
+  readonly name = "StakesNotCounted"
+  readonly msg = "Farm still has stakes made before they were counted."
+
+  constructor(readonly logs?: string[]) {
+    super("6026: Farm still has stakes made before they were counted.")
+  }
+}
+
+export class SecondaryRewardOwed extends Error {
+  static readonly code = 6027
+  readonly code = 6027
+  readonly name = "SecondaryRewardOwed"
+  readonly msg = "Farmers still earn or are owed secondary rewards."
+
+  constructor(readonly logs?: string[]) {
+    super("6027: Farmers still earn or are owed secondary rewards.")
+  }
+}
+
+export class FarmNotClosed extends Error {
+  static readonly code = 6028
+  readonly code = 6028
+  readonly name = "FarmNotClosed"
+  readonly msg = "Farm is still open."
+
+  constructor(readonly logs?: string[]) {
+    super("6028: Farm is still open.")
+  }
+}
+
 export function fromCode(code: number, logs?: string[]): CustomError | null {
   switch (code) {
     case 6000:
@@ -128,6 +374,46 @@ export function fromCode(code: number, logs?: string[]): CustomError | null {
       return new FactorMustBeGtZero(logs)
     case 6008:
       return new ArithmeticError(logs)
+    case 6009:
+      return new CouldNotWithdrawReward(logs)
+    case 6010:
+      return new InvalidMerkleProof(logs)
+    case 6011:
+      return new InvalidRewardWindow(logs)
+    case 6012:
+      return new MissingPermission(logs)
+    case 6013:
+      return new InvalidPermissions(logs)
+    case 6014:
+      return new InvalidSecondaryReward(logs)
+    case 6015:
+      return new InvalidSecondaryRewardAccounts(logs)
+    case 6016:
+      return new InvalidUnstakeAmount(logs)
+    case 6017:
+      return new UnsupportedTokenStandard(logs)
+    case 6018:
+      return new InvalidBatchAccounts(logs)
+    case 6019:
+      return new FarmerNotEmpty(logs)
+    case 6020:
+      return new StakingPaused(logs)
+    case 6021:
+      return new FarmFrozen(logs)
+    case 6022:
+      return new FarmNotEmpty(logs)
+    case 6023:
+      return new AccountAlreadyMigrated(logs)
+    case 6024:
+      return new WhitelistMismatch(logs)
+    case 6025:
+      return new StakeUpToDate(logs)
+    case 6026:
+      return new StakesNotCounted(logs)
+    case 6027:
+      return new SecondaryRewardOwed(logs)
+    case 6028:
+      return new FarmNotClosed(logs)
   }
 
   return null
@@ -0,0 +1,27 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface AcceptAuthorityAccounts {
+  farm: PublicKey
+  authorityTransfer: PublicKey
+  previousFarmManager: PublicKey
+  previousAuthority: PublicKey
+  newAuthority: PublicKey
+}
+
+export function acceptAuthority(accounts: AcceptAuthorityAccounts) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farm, isSigner: false, isWritable: true },
+    { pubkey: accounts.authorityTransfer, isSigner: false, isWritable: true },
+    { pubkey: accounts.previousFarmManager, isSigner: false, isWritable: true },
+    { pubkey: accounts.previousAuthority, isSigner: false, isWritable: true },
+    { pubkey: accounts.newAuthority, isSigner: true, isWritable: false },
+  ]
+  const identifier = Buffer.from([107, 86, 198, 91, 33, 12, 107, 160])
+  const data = identifier
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
@@ -4,6 +4,10 @@ import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript
 import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
 import { PROGRAM_ID } from "../programId"
 
+export interface AddManagerArgs {
+  permissions: number
+}
+
 export interface AddManagerAccounts {
   farm: PublicKey
   farmManager: PublicKey
@@ -12,7 +16,9 @@ export interface AddManagerAccounts {
   systemProgram: PublicKey
 }
 
-export function addManager(accounts: AddManagerAccounts) {
+export const layout = borsh.struct([borsh.u8("permissions")])
+
+export function addManager(args: AddManagerArgs, accounts: AddManagerAccounts) {
   const keys: Array<AccountMeta> = [
     { pubkey: accounts.farm, isSigner: false, isWritable: false },
     { pubkey: accounts.farmManager, isSigner: false, isWritable: true },
@@ -21,7 +27,14 @@ export function addManager(accounts: AddManagerAccounts) {
     { pubkey: accounts.systemProgram, isSigner: false, isWritable: false },
   ]
   const identifier = Buffer.from([125, 38, 192, 212, 101, 91, 179, 16])
-  const data = identifier
+  const buffer = Buffer.alloc(1000)
+  const len = layout.encode(
+    {
+      permissions: args.permissions,
+    },
+    buffer
+  )
+  const data = Buffer.concat([identifier, buffer]).slice(0, 8 + len)
   const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
   return ix
 }
@@ -7,6 +7,9 @@ import { PROGRAM_ID } from "../programId"
 export interface AddToWhitelistArgs {
   rewardRate: BN
   whitelistType: types.WhitelistTypeKind
+  minStakeDuration: BN
+  unstakeCooldown: number
+  secondaryRewardRate: BN
 }
 
 export interface AddToWhitelistAccounts {
@@ -21,6 +24,9 @@ export interface AddToWhitelistAccounts {
 export const layout = borsh.struct([
   borsh.u64("rewardRate"),
   types.WhitelistType.layout("whitelistType"),
+  borsh.u64("minStakeDuration"),
+  borsh.u32("unstakeCooldown"),
+  borsh.u64("secondaryRewardRate"),
 ])
 
 export function addToWhitelist(
@@ -41,6 +47,9 @@ export function addToWhitelist(
     {
       rewardRate: args.rewardRate,
       whitelistType: args.whitelistType.toEncodable(),
+      minStakeDuration: args.minStakeDuration,
+      unstakeCooldown: args.unstakeCooldown,
+      secondaryRewardRate: args.secondaryRewardRate,
     },
     buffer
   )
@@ -0,0 +1,29 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface BeginUnstakeAccounts {
+  farm: PublicKey
+  farmer: PublicKey
+  secondaryReward: PublicKey
+  gemMint: PublicKey
+  stakeReceipt: PublicKey
+  owner: PublicKey
+}
+
+export function beginUnstake(accounts: BeginUnstakeAccounts) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farm, isSigner: false, isWritable: true },
+    { pubkey: accounts.farmer, isSigner: false, isWritable: true },
+    { pubkey: accounts.secondaryReward, isSigner: false, isWritable: true },
+    { pubkey: accounts.gemMint, isSigner: false, isWritable: false },
+    { pubkey: accounts.stakeReceipt, isSigner: false, isWritable: true },
+    { pubkey: accounts.owner, isSigner: true, isWritable: false },
+  ]
+  const identifier = Buffer.from([156, 67, 177, 83, 28, 111, 174, 132])
+  const data = identifier
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
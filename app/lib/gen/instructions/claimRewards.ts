@@ -7,6 +7,7 @@ import { PROGRAM_ID } from "../programId"
 export interface ClaimRewardsAccounts {
   farm: PublicKey
   farmer: PublicKey
+  secondaryReward: PublicKey
   rewardMint: PublicKey
   farmRewardVault: PublicKey
   farmerRewardVault: PublicKey
@@ -21,6 +22,7 @@ export function claimRewards(accounts: ClaimRewardsAccounts) {
   const keys: Array<AccountMeta> = [
     { pubkey: accounts.farm, isSigner: false, isWritable: true },
     { pubkey: accounts.farmer, isSigner: false, isWritable: true },
+    { pubkey: accounts.secondaryReward, isSigner: false, isWritable: true },
     { pubkey: accounts.rewardMint, isSigner: false, isWritable: false },
     { pubkey: accounts.farmRewardVault, isSigner: false, isWritable: true },
     { pubkey: accounts.farmerRewardVault, isSigner: false, isWritable: true },
@@ -0,0 +1,23 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface CloseStakeReceiptAccounts {
+  farmer: PublicKey
+  stakeReceipt: PublicKey
+  owner: PublicKey
+}
+
+export function closeStakeReceipt(accounts: CloseStakeReceiptAccounts) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farmer, isSigner: false, isWritable: false },
+    { pubkey: accounts.stakeReceipt, isSigner: false, isWritable: true },
+    { pubkey: accounts.owner, isSigner: true, isWritable: true },
+  ]
+  const identifier = Buffer.from([15, 45, 184, 212, 252, 131, 133, 126])
+  const data = identifier
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
@@ -0,0 +1,39 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface ExtendRewardWindowArgs {
+  endTs: BN
+}
+
+export interface ExtendRewardWindowAccounts {
+  farm: PublicKey
+  farmManager: PublicKey
+  authority: PublicKey
+}
+
+export const layout = borsh.struct([borsh.u64("endTs")])
+
+export function extendRewardWindow(
+  args: ExtendRewardWindowArgs,
+  accounts: ExtendRewardWindowAccounts
+) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farm, isSigner: false, isWritable: true },
+    { pubkey: accounts.farmManager, isSigner: false, isWritable: false },
+    { pubkey: accounts.authority, isSigner: true, isWritable: false },
+  ]
+  const identifier = Buffer.from([126, 65, 93, 201, 0, 28, 0, 9])
+  const buffer = Buffer.alloc(1000)
+  const len = layout.encode(
+    {
+      endTs: args.endTs,
+    },
+    buffer
+  )
+  const data = Buffer.concat([identifier, buffer]).slice(0, 8 + len)
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
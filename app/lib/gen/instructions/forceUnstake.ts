@@ -6,7 +6,9 @@ import { PROGRAM_ID } from "../programId"
 
 export interface ForceUnstakeAccounts {
   farm: PublicKey
+  farmManager: PublicKey
   farmer: PublicKey
+  secondaryReward: PublicKey
   gemMint: PublicKey
   stakeReceipt: PublicKey
   farmerVault: PublicKey
@@ -22,12 +24,14 @@ export interface ForceUnstakeAccounts {
 export function forceUnstake(accounts: ForceUnstakeAccounts) {
   const keys: Array<AccountMeta> = [
     { pubkey: accounts.farm, isSigner: false, isWritable: true },
+    { pubkey: accounts.farmManager, isSigner: false, isWritable: false },
     { pubkey: accounts.farmer, isSigner: false, isWritable: true },
+    { pubkey: accounts.secondaryReward, isSigner: false, isWritable: true },
     { pubkey: accounts.gemMint, isSigner: false, isWritable: false },
     { pubkey: accounts.stakeReceipt, isSigner: false, isWritable: true },
     { pubkey: accounts.farmerVault, isSigner: false, isWritable: true },
     { pubkey: accounts.gemOwnerAta, isSigner: false, isWritable: true },
-    { pubkey: accounts.owner, isSigner: false, isWritable: false },
+    { pubkey: accounts.owner, isSigner: false, isWritable: true },
     { pubkey: accounts.authority, isSigner: true, isWritable: true },
     { pubkey: accounts.rent, isSigner: false, isWritable: false },
     { pubkey: accounts.systemProgram, isSigner: false, isWritable: false },
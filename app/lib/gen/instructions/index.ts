@@ -1,23 +1,107 @@
 export { createFarm } from "./createFarm"
 export type { CreateFarmAccounts } from "./createFarm"
+export { addBuff } from "./addBuff"
+export type { AddBuffArgs, AddBuffAccounts } from "./addBuff"
+export { removeBuff } from "./removeBuff"
+export type { RemoveBuffAccounts } from "./removeBuff"
 export { addManager } from "./addManager"
-export type { AddManagerAccounts } from "./addManager"
+export type { AddManagerArgs, AddManagerAccounts } from "./addManager"
+export { removeManager } from "./removeManager"
+export type { RemoveManagerAccounts } from "./removeManager"
+export { migrateFarmManager } from "./migrateFarmManager"
+export type { MigrateFarmManagerAccounts } from "./migrateFarmManager"
+export { proposeAuthority } from "./proposeAuthority"
+export type { ProposeAuthorityAccounts } from "./proposeAuthority"
+export { acceptAuthority } from "./acceptAuthority"
+export type { AcceptAuthorityAccounts } from "./acceptAuthority"
+export { cancelAuthorityTransfer } from "./cancelAuthorityTransfer"
+export type { CancelAuthorityTransferAccounts } from "./cancelAuthorityTransfer"
 export { addToWhitelist } from "./addToWhitelist"
 export type {
   AddToWhitelistArgs,
   AddToWhitelistAccounts,
 } from "./addToWhitelist"
+export { updateWhitelist } from "./updateWhitelist"
+export type {
+  UpdateWhitelistArgs,
+  UpdateWhitelistAccounts,
+} from "./updateWhitelist"
+export { migrateWhitelistProof } from "./migrateWhitelistProof"
+export type { MigrateWhitelistProofAccounts } from "./migrateWhitelistProof"
 export { removeFromWhitelist } from "./removeFromWhitelist"
 export type { RemoveFromWhitelistAccounts } from "./removeFromWhitelist"
 export { fundReward } from "./fundReward"
 export type { FundRewardArgs, FundRewardAccounts } from "./fundReward"
+export { addSecondaryReward } from "./addSecondaryReward"
+export type { AddSecondaryRewardAccounts } from "./addSecondaryReward"
+export { fundSecondaryReward } from "./fundSecondaryReward"
+export type {
+  FundSecondaryRewardArgs,
+  FundSecondaryRewardAccounts,
+} from "./fundSecondaryReward"
+export { withdrawSecondaryReward } from "./withdrawSecondaryReward"
+export type {
+  WithdrawSecondaryRewardArgs,
+  WithdrawSecondaryRewardAccounts,
+} from "./withdrawSecondaryReward"
+export { closeSecondaryReward } from "./closeSecondaryReward"
+export type { CloseSecondaryRewardAccounts } from "./closeSecondaryReward"
+export { withdrawReward } from "./withdrawReward"
+export type {
+  WithdrawRewardArgs,
+  WithdrawRewardAccounts,
+} from "./withdrawReward"
+export { setAccrualPolicy } from "./setAccrualPolicy"
+export type {
+  SetAccrualPolicyArgs,
+  SetAccrualPolicyAccounts,
+} from "./setAccrualPolicy"
+export { setFarmState } from "./setFarmState"
+export type { SetFarmStateArgs, SetFarmStateAccounts } from "./setFarmState"
+export { finishStakeMigration } from "./finishStakeMigration"
+export type { FinishStakeMigrationAccounts } from "./finishStakeMigration"
+export { closeFarm } from "./closeFarm"
+export type { CloseFarmAccounts } from "./closeFarm"
+export { setRewardWindow } from "./setRewardWindow"
+export type {
+  SetRewardWindowArgs,
+  SetRewardWindowAccounts,
+} from "./setRewardWindow"
+export { extendRewardWindow } from "./extendRewardWindow"
+export type {
+  ExtendRewardWindowArgs,
+  ExtendRewardWindowAccounts,
+} from "./extendRewardWindow"
 export { initializeFarmer } from "./initializeFarmer"
 export type { InitializeFarmerAccounts } from "./initializeFarmer"
 export { stake } from "./stake"
 export type { StakeArgs, StakeAccounts } from "./stake"
+export { stakeMany } from "./stakeMany"
+export type { StakeManyArgs, StakeManyAccounts } from "./stakeMany"
+export { refreshStake } from "./refreshStake"
+export type { RefreshStakeAccounts } from "./refreshStake"
+export { migrateStake } from "./migrateStake"
+export type { MigrateStakeArgs, MigrateStakeAccounts } from "./migrateStake"
+export { beginUnstake } from "./beginUnstake"
+export type { BeginUnstakeAccounts } from "./beginUnstake"
 export { unstake } from "./unstake"
 export type { UnstakeAccounts } from "./unstake"
+export { unstakeAndClaim } from "./unstakeAndClaim"
+export type { UnstakeAndClaimAccounts } from "./unstakeAndClaim"
+export { unstakeMany } from "./unstakeMany"
+export type { UnstakeManyAccounts } from "./unstakeMany"
+export { partialUnstake } from "./partialUnstake"
+export type {
+  PartialUnstakeArgs,
+  PartialUnstakeAccounts,
+} from "./partialUnstake"
 export { claimRewards } from "./claimRewards"
 export type { ClaimRewardsAccounts } from "./claimRewards"
+export { closeStakeReceipt } from "./closeStakeReceipt"
+export type { CloseStakeReceiptAccounts } from "./closeStakeReceipt"
+export { closeFarmer } from "./closeFarmer"
+export type { CloseFarmerAccounts } from "./closeFarmer"
 export { forceUnstake } from "./forceUnstake"
 export type { ForceUnstakeAccounts } from "./forceUnstake"
+export { withdrawOrphanedStake } from "./withdrawOrphanedStake"
+export type { WithdrawOrphanedStakeAccounts } from "./withdrawOrphanedStake"
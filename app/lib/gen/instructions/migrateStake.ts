@@ -0,0 +1,53 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface MigrateStakeArgs {
+  merkleProof: Array<Array<number>>
+}
+
+export interface MigrateStakeAccounts {
+  farm: PublicKey
+  farmManager: PublicKey
+  farmer: PublicKey
+  secondaryReward: PublicKey
+  gemMint: PublicKey
+  stakeReceipt: PublicKey
+  whitelistProof: PublicKey
+  buffProof: PublicKey
+  authority: PublicKey
+}
+
+export const layout = borsh.struct([
+  borsh.vec(borsh.array(borsh.u8(), 32), "merkleProof"),
+])
+
+export function migrateStake(
+  args: MigrateStakeArgs,
+  accounts: MigrateStakeAccounts
+) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farm, isSigner: false, isWritable: true },
+    { pubkey: accounts.farmManager, isSigner: false, isWritable: false },
+    { pubkey: accounts.farmer, isSigner: false, isWritable: true },
+    { pubkey: accounts.secondaryReward, isSigner: false, isWritable: true },
+    { pubkey: accounts.gemMint, isSigner: false, isWritable: false },
+    { pubkey: accounts.stakeReceipt, isSigner: false, isWritable: true },
+    { pubkey: accounts.whitelistProof, isSigner: false, isWritable: false },
+    { pubkey: accounts.buffProof, isSigner: false, isWritable: false },
+    { pubkey: accounts.authority, isSigner: true, isWritable: false },
+  ]
+  const identifier = Buffer.from([178, 5, 26, 85, 56, 20, 153, 160])
+  const buffer = Buffer.alloc(1000)
+  const len = layout.encode(
+    {
+      merkleProof: args.merkleProof,
+    },
+    buffer
+  )
+  const data = Buffer.concat([identifier, buffer]).slice(0, 8 + len)
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
@@ -0,0 +1,37 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface SetAccrualPolicyArgs {
+  policy: types.AccrualPolicyKind
+}
+
+export interface SetAccrualPolicyAccounts {
+  farm: PublicKey
+  authority: PublicKey
+}
+
+export const layout = borsh.struct([types.AccrualPolicy.layout("policy")])
+
+export function setAccrualPolicy(
+  args: SetAccrualPolicyArgs,
+  accounts: SetAccrualPolicyAccounts
+) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farm, isSigner: false, isWritable: true },
+    { pubkey: accounts.authority, isSigner: true, isWritable: false },
+  ]
+  const identifier = Buffer.from([0, 12, 206, 117, 213, 71, 79, 138])
+  const buffer = Buffer.alloc(1000)
+  const len = layout.encode(
+    {
+      policy: args.policy.toEncodable(),
+    },
+    buffer
+  )
+  const data = Buffer.concat([identifier, buffer]).slice(0, 8 + len)
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
@@ -0,0 +1,37 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface SetFarmStateArgs {
+  state: types.FarmStateKind
+}
+
+export interface SetFarmStateAccounts {
+  farm: PublicKey
+  authority: PublicKey
+}
+
+export const layout = borsh.struct([types.FarmState.layout("state")])
+
+export function setFarmState(
+  args: SetFarmStateArgs,
+  accounts: SetFarmStateAccounts
+) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farm, isSigner: false, isWritable: true },
+    { pubkey: accounts.authority, isSigner: true, isWritable: false },
+  ]
+  const identifier = Buffer.from([57, 76, 29, 115, 176, 33, 3, 124])
+  const buffer = Buffer.alloc(1000)
+  const len = layout.encode(
+    {
+      state: args.state.toEncodable(),
+    },
+    buffer
+  )
+  const data = Buffer.concat([identifier, buffer]).slice(0, 8 + len)
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
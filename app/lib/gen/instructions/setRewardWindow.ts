@@ -0,0 +1,39 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface SetRewardWindowArgs {
+  startTs: BN
+  endTs: BN
+}
+
+export interface SetRewardWindowAccounts {
+  farm: PublicKey
+  authority: PublicKey
+}
+
+export const layout = borsh.struct([borsh.u64("startTs"), borsh.u64("endTs")])
+
+export function setRewardWindow(
+  args: SetRewardWindowArgs,
+  accounts: SetRewardWindowAccounts
+) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farm, isSigner: false, isWritable: true },
+    { pubkey: accounts.authority, isSigner: true, isWritable: false },
+  ]
+  const identifier = Buffer.from([104, 44, 107, 248, 52, 107, 151, 186])
+  const buffer = Buffer.alloc(1000)
+  const len = layout.encode(
+    {
+      startTs: args.startTs,
+      endTs: args.endTs,
+    },
+    buffer
+  )
+  const data = Buffer.concat([identifier, buffer]).slice(0, 8 + len)
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
@@ -6,14 +6,16 @@ import { PROGRAM_ID } from "../programId"
 
 export interface StakeArgs {
   amount: BN
-  hasEssence: boolean
+  merkleProof: Array<Array<number>>
 }
 
 export interface StakeAccounts {
   farm: PublicKey
   farmer: PublicKey
+  secondaryReward: PublicKey
   gemMint: PublicKey
   whitelistProof: PublicKey
+  buffProof: PublicKey
   farmerVault: PublicKey
   gemOwnerAta: PublicKey
   stakeReceipt: PublicKey
@@ -26,15 +28,17 @@ export interface StakeAccounts {
 
 export const layout = borsh.struct([
   borsh.u64("amount"),
-  borsh.bool("hasEssence"),
+  borsh.vec(borsh.array(borsh.u8(), 32), "merkleProof"),
 ])
 
 export function stake(args: StakeArgs, accounts: StakeAccounts) {
   const keys: Array<AccountMeta> = [
     { pubkey: accounts.farm, isSigner: false, isWritable: true },
     { pubkey: accounts.farmer, isSigner: false, isWritable: true },
+    { pubkey: accounts.secondaryReward, isSigner: false, isWritable: true },
     { pubkey: accounts.gemMint, isSigner: false, isWritable: false },
     { pubkey: accounts.whitelistProof, isSigner: false, isWritable: false },
+    { pubkey: accounts.buffProof, isSigner: false, isWritable: false },
     { pubkey: accounts.farmerVault, isSigner: false, isWritable: true },
     { pubkey: accounts.gemOwnerAta, isSigner: false, isWritable: true },
     { pubkey: accounts.stakeReceipt, isSigner: false, isWritable: true },
@@ -53,7 +57,7 @@ export function stake(args: StakeArgs, accounts: StakeAccounts) {
   const len = layout.encode(
     {
       amount: args.amount,
-      hasEssence: args.hasEssence,
+      merkleProof: args.merkleProof,
     },
     buffer
   )
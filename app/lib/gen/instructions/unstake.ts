@@ -7,6 +7,7 @@ import { PROGRAM_ID } from "../programId"
 export interface UnstakeAccounts {
   farm: PublicKey
   farmer: PublicKey
+  secondaryReward: PublicKey
   gemMint: PublicKey
   stakeReceipt: PublicKey
   farmerVault: PublicKey
@@ -22,6 +23,7 @@ export function unstake(accounts: UnstakeAccounts) {
   const keys: Array<AccountMeta> = [
     { pubkey: accounts.farm, isSigner: false, isWritable: true },
     { pubkey: accounts.farmer, isSigner: false, isWritable: true },
+    { pubkey: accounts.secondaryReward, isSigner: false, isWritable: true },
     { pubkey: accounts.gemMint, isSigner: false, isWritable: false },
     { pubkey: accounts.stakeReceipt, isSigner: false, isWritable: true },
     { pubkey: accounts.farmerVault, isSigner: false, isWritable: true },
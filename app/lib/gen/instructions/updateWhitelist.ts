@@ -0,0 +1,46 @@
+import { TransactionInstruction, PublicKey, AccountMeta } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import { PROGRAM_ID } from "../programId"
+
+export interface UpdateWhitelistArgs {
+  rewardRate: BN
+  secondaryRewardRate: BN
+}
+
+export interface UpdateWhitelistAccounts {
+  farm: PublicKey
+  farmManager: PublicKey
+  whitelistProof: PublicKey
+  authority: PublicKey
+}
+
+export const layout = borsh.struct([
+  borsh.u64("rewardRate"),
+  borsh.u64("secondaryRewardRate"),
+])
+
+export function updateWhitelist(
+  args: UpdateWhitelistArgs,
+  accounts: UpdateWhitelistAccounts
+) {
+  const keys: Array<AccountMeta> = [
+    { pubkey: accounts.farm, isSigner: false, isWritable: false },
+    { pubkey: accounts.farmManager, isSigner: false, isWritable: false },
+    { pubkey: accounts.whitelistProof, isSigner: false, isWritable: true },
+    { pubkey: accounts.authority, isSigner: true, isWritable: false },
+  ]
+  const identifier = Buffer.from([94, 198, 33, 20, 192, 97, 44, 59])
+  const buffer = Buffer.alloc(1000)
+  const len = layout.encode(
+    {
+      rewardRate: args.rewardRate,
+      secondaryRewardRate: args.secondaryRewardRate,
+    },
+    buffer
+  )
+  const data = Buffer.concat([identifier, buffer]).slice(0, 8 + len)
+  const ix = new TransactionInstruction({ keys, programId: PROGRAM_ID, data })
+  return ix
+}
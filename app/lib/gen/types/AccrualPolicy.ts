@@ -0,0 +1,120 @@
+import { PublicKey } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh"
+
+export interface FailJSON {
+  kind: "Fail"
+}
+
+export class Fail {
+  static readonly discriminator = 0
+  static readonly kind = "Fail"
+  readonly discriminator = 0
+  readonly kind = "Fail"
+
+  toJSON(): FailJSON {
+    return {
+      kind: "Fail",
+    }
+  }
+
+  toEncodable() {
+    return {
+      Fail: {},
+    }
+  }
+}
+
+export interface PartialJSON {
+  kind: "Partial"
+}
+
+export class Partial {
+  static readonly discriminator = 1
+  static readonly kind = "Partial"
+  readonly discriminator = 1
+  readonly kind = "Partial"
+
+  toJSON(): PartialJSON {
+    return {
+      kind: "Partial",
+    }
+  }
+
+  toEncodable() {
+    return {
+      Partial: {},
+    }
+  }
+}
+
+export interface SkipJSON {
+  kind: "Skip"
+}
+
+export class Skip {
+  static readonly discriminator = 2
+  static readonly kind = "Skip"
+  readonly discriminator = 2
+  readonly kind = "Skip"
+
+  toJSON(): SkipJSON {
+    return {
+      kind: "Skip",
+    }
+  }
+
+  toEncodable() {
+    return {
+      Skip: {},
+    }
+  }
+}
+
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+export function fromDecoded(obj: any): types.AccrualPolicyKind {
+  if (typeof obj !== "object") {
+    throw new Error("Invalid enum object")
+  }
+
+  if ("Fail" in obj) {
+    return new Fail()
+  }
+  if ("Partial" in obj) {
+    return new Partial()
+  }
+  if ("Skip" in obj) {
+    return new Skip()
+  }
+
+  throw new Error("Invalid enum object")
+}
+
+export function fromJSON(
+  obj: types.AccrualPolicyJSON
+): types.AccrualPolicyKind {
+  switch (obj.kind) {
+    case "Fail": {
+      return new Fail()
+    }
+    case "Partial": {
+      return new Partial()
+    }
+    case "Skip": {
+      return new Skip()
+    }
+  }
+}
+
+export function layout(property?: string) {
+  const ret = borsh.rustEnum([
+    borsh.struct([], "Fail"),
+    borsh.struct([], "Partial"),
+    borsh.struct([], "Skip"),
+  ])
+  if (property !== undefined) {
+    return ret.replicate(property)
+  }
+  return ret
+}
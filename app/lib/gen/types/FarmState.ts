@@ -0,0 +1,148 @@
+import { PublicKey } from "@solana/web3.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import BN from "bn.js" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as types from "../types" // eslint-disable-line @typescript-eslint/no-unused-vars
+import * as borsh from "@project-serum/borsh"
+
+export interface ActiveJSON {
+  kind: "Active"
+}
+
+export class Active {
+  static readonly discriminator = 0
+  static readonly kind = "Active"
+  readonly discriminator = 0
+  readonly kind = "Active"
+
+  toJSON(): ActiveJSON {
+    return {
+      kind: "Active",
+    }
+  }
+
+  toEncodable() {
+    return {
+      Active: {},
+    }
+  }
+}
+
+export interface StakingPausedJSON {
+  kind: "StakingPaused"
+}
+
+export class StakingPaused {
+  static readonly discriminator = 1
+  static readonly kind = "StakingPaused"
+  readonly discriminator = 1
+  readonly kind = "StakingPaused"
+
+  toJSON(): StakingPausedJSON {
+    return {
+      kind: "StakingPaused",
+    }
+  }
+
+  toEncodable() {
+    return {
+      StakingPaused: {},
+    }
+  }
+}
+
+export interface RewardsPausedJSON {
+  kind: "RewardsPaused"
+}
+
+export class RewardsPaused {
+  static readonly discriminator = 2
+  static readonly kind = "RewardsPaused"
+  readonly discriminator = 2
+  readonly kind = "RewardsPaused"
+
+  toJSON(): RewardsPausedJSON {
+    return {
+      kind: "RewardsPaused",
+    }
+  }
+
+  toEncodable() {
+    return {
+      RewardsPaused: {},
+    }
+  }
+}
+
+export interface FrozenJSON {
+  kind: "Frozen"
+}
+
+export class Frozen {
+  static readonly discriminator = 3
+  static readonly kind = "Frozen"
+  readonly discriminator = 3
+  readonly kind = "Frozen"
+
+  toJSON(): FrozenJSON {
+    return {
+      kind: "Frozen",
+    }
+  }
+
+  toEncodable() {
+    return {
+      Frozen: {},
+    }
+  }
+}
+
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+export function fromDecoded(obj: any): types.FarmStateKind {
+  if (typeof obj !== "object") {
+    throw new Error("Invalid enum object")
+  }
+
+  if ("Active" in obj) {
+    return new Active()
+  }
+  if ("StakingPaused" in obj) {
+    return new StakingPaused()
+  }
+  if ("RewardsPaused" in obj) {
+    return new RewardsPaused()
+  }
+  if ("Frozen" in obj) {
+    return new Frozen()
+  }
+
+  throw new Error("Invalid enum object")
+}
+
+export function fromJSON(obj: types.FarmStateJSON): types.FarmStateKind {
+  switch (obj.kind) {
+    case "Active": {
+      return new Active()
+    }
+    case "StakingPaused": {
+      return new StakingPaused()
+    }
+    case "RewardsPaused": {
+      return new RewardsPaused()
+    }
+    case "Frozen": {
+      return new Frozen()
+    }
+  }
+}
+
+export function layout(property?: string) {
+  const ret = borsh.rustEnum([
+    borsh.struct([], "Active"),
+    borsh.struct([], "StakingPaused"),
+    borsh.struct([], "RewardsPaused"),
+    borsh.struct([], "Frozen"),
+  ])
+  if (property !== undefined) {
+    return ret.replicate(property)
+  }
+  return ret
+}
@@ -49,6 +49,52 @@ export class Mint {
   }
 }
 
+export interface MerkleRootJSON {
+  kind: "MerkleRoot"
+}
+
+export class MerkleRoot {
+  static readonly discriminator = 2
+  static readonly kind = "MerkleRoot"
+  readonly discriminator = 2
+  readonly kind = "MerkleRoot"
+
+  toJSON(): MerkleRootJSON {
+    return {
+      kind: "MerkleRoot",
+    }
+  }
+
+  toEncodable() {
+    return {
+      MerkleRoot: {},
+    }
+  }
+}
+
+export interface CollectionJSON {
+  kind: "Collection"
+}
+
+export class Collection {
+  static readonly discriminator = 3
+  static readonly kind = "Collection"
+  readonly discriminator = 3
+  readonly kind = "Collection"
+
+  toJSON(): CollectionJSON {
+    return {
+      kind: "Collection",
+    }
+  }
+
+  toEncodable() {
+    return {
+      Collection: {},
+    }
+  }
+}
+
 // eslint-disable-next-line @typescript-eslint/no-explicit-any
 export function fromDecoded(obj: any): types.WhitelistTypeKind {
   if (typeof obj !== "object") {
@@ -61,6 +107,12 @@ export function fromDecoded(obj: any): types.WhitelistTypeKind {
   if ("Mint" in obj) {
     return new Mint()
   }
+  if ("MerkleRoot" in obj) {
+    return new MerkleRoot()
+  }
+  if ("Collection" in obj) {
+    return new Collection()
+  }
 
   throw new Error("Invalid enum object")
 }
@@ -75,6 +127,12 @@ export function fromJSON(
     case "Mint": {
       return new Mint()
     }
+    case "MerkleRoot": {
+      return new MerkleRoot()
+    }
+    case "Collection": {
+      return new Collection()
+    }
   }
 }
 
@@ -82,6 +140,8 @@ export function layout(property?: string) {
   const ret = borsh.rustEnum([
     borsh.struct([], "Creator"),
     borsh.struct([], "Mint"),
+    borsh.struct([], "MerkleRoot"),
+    borsh.struct([], "Collection"),
   ])
   if (property !== undefined) {
     return ret.replicate(property)
@@ -1,10 +1,42 @@
+import * as AccrualPolicy from "./AccrualPolicy"
+import * as FarmState from "./FarmState"
 import * as WhitelistType from "./WhitelistType"
 
 export { Reward } from "./Reward"
 export type { RewardFields, RewardJSON } from "./Reward"
+export { AccrualPolicy }
+
+export type AccrualPolicyKind =
+  | AccrualPolicy.Fail
+  | AccrualPolicy.Partial
+  | AccrualPolicy.Skip
+export type AccrualPolicyJSON =
+  | AccrualPolicy.FailJSON
+  | AccrualPolicy.PartialJSON
+  | AccrualPolicy.SkipJSON
+
+export { FarmState }
+
+export type FarmStateKind =
+  | FarmState.Active
+  | FarmState.StakingPaused
+  | FarmState.RewardsPaused
+  | FarmState.Frozen
+export type FarmStateJSON =
+  | FarmState.ActiveJSON
+  | FarmState.StakingPausedJSON
+  | FarmState.RewardsPausedJSON
+  | FarmState.FrozenJSON
+
 export { WhitelistType }
 
-export type WhitelistTypeKind = WhitelistType.Creator | WhitelistType.Mint
+export type WhitelistTypeKind =
+  | WhitelistType.Creator
+  | WhitelistType.Mint
+  | WhitelistType.MerkleRoot
+  | WhitelistType.Collection
 export type WhitelistTypeJSON =
   | WhitelistType.CreatorJSON
   | WhitelistType.MintJSON
+  | WhitelistType.MerkleRootJSON
+  | WhitelistType.CollectionJSON
@@ -6,7 +6,7 @@ import {
   SYSVAR_RENT_PUBKEY,
 } from "@solana/web3.js"
 
-import { Farm } from "./gen/accounts"
+import { Farm, SecondaryReward } from "./gen/accounts"
 import {
   addManager,
   addToWhitelist,
@@ -15,19 +15,25 @@ import {
   forceUnstake,
   fundReward,
   initializeFarmer,
+  partialUnstake,
   removeFromWhitelist,
   stake,
   unstake,
 } from "./gen/instructions"
 import { WhitelistTypeKind } from "./gen/types"
 import {
+  findBuffProofAddress,
   findWhitelistProofAddress,
   findFarmAddress,
   findFarmerAddress,
   findFarmManagerAddress,
+  findSecondaryRewardAddress,
   findStakeReceiptAddress,
 } from "./pda"
-import { hasBuffAttribute, tryFindCreator } from "./utils"
+import { tryFindCreator } from "./utils"
+
+/** Mirrors `FarmManager::ALL` on-chain. */
+export const ALL_PERMISSIONS = 0b1111
 
 interface ICreateFarm {
   authority: PublicKey
@@ -43,6 +49,9 @@ interface IAddToWhitelist {
     tokenAmount: BN
     intervalInSeconds: BN
   }
+  minStakeDuration?: BN
+  unstakeCooldown?: number
+  secondaryRewardRate?: BN
 }
 
 interface IRemoveFromWhitelist {
@@ -61,6 +70,7 @@ interface IAddManager {
   farm: PublicKey
   newManagerAuthority: PublicKey
   farmAuthority: PublicKey
+  permissions?: number
 }
 
 interface IInitializeFarmer {
@@ -73,6 +83,10 @@ interface IStake {
   mint: PublicKey
   amount: BN
   owner: PublicKey
+  // Root and sibling hashes when the mint is whitelisted through a
+  // `MerkleRoot` whitelist.
+  merkleRoot?: PublicKey
+  merkleProof?: Buffer[]
 }
 
 interface IUnstake {
@@ -81,6 +95,13 @@ interface IUnstake {
   owner: PublicKey
 }
 
+interface IPartialUnstake {
+  farm: PublicKey
+  mint: PublicKey
+  amount: BN
+  owner: PublicKey
+}
+
 interface IForceUnstake {
   farm: PublicKey
   farmAuthority: PublicKey
@@ -130,13 +151,16 @@ export const StakingProgram = (connection: Connection) => {
       associatedTokenProgram,
     })
 
-    const addManagerIx = addManager({
-      farm,
-      farmManager,
-      authority,
-      managerAuthority: authority,
-      systemProgram,
-    })
+    const addManagerIx = addManager(
+      { permissions: ALL_PERMISSIONS },
+      {
+        farm,
+        farmManager,
+        authority,
+        managerAuthority: authority,
+        systemProgram,
+      }
+    )
 
     return { ix: [createFarmIx, addManagerIx] }
   }
@@ -145,19 +169,23 @@ export const StakingProgram = (connection: Connection) => {
     farm,
     farmAuthority,
     newManagerAuthority,
+    permissions = ALL_PERMISSIONS,
   }: IAddManager) => {
     const farmManager = findFarmManagerAddress({
       farm,
       authority: newManagerAuthority,
     })
 
-    const ix = addManager({
-      farm,
-      farmManager,
-      managerAuthority: newManagerAuthority,
-      authority: farmAuthority,
-      systemProgram,
-    })
+    const ix = addManager(
+      { permissions },
+      {
+        farm,
+        farmManager,
+        managerAuthority: newManagerAuthority,
+        authority: farmAuthority,
+        systemProgram,
+      }
+    )
 
     return { ix }
   }
@@ -206,6 +234,9 @@ export const StakingProgram = (connection: Connection) => {
     authority,
     rewardRate,
     whitelistType,
+    minStakeDuration = new BN(0),
+    unstakeCooldown = 0,
+    secondaryRewardRate = new BN(0),
   }: IAddToWhitelist) => {
     const farmManager = findFarmManagerAddress({
       farm,
@@ -216,7 +247,13 @@ export const StakingProgram = (connection: Connection) => {
     const { tokenAmount, intervalInSeconds } = rewardRate
 
     const ix = addToWhitelist(
-      { rewardRate: tokenAmount.div(intervalInSeconds), whitelistType },
+      {
+        rewardRate: tokenAmount.div(intervalInSeconds),
+        whitelistType,
+        minStakeDuration,
+        unstakeCooldown,
+        secondaryRewardRate,
+      },
       {
         farm,
         farmManager,
@@ -273,6 +310,8 @@ export const StakingProgram = (connection: Connection) => {
     farm,
     mint,
     amount,
+    merkleRoot,
+    merkleProof = [],
   }: IStake) => {
     const farmer = findFarmerAddress({ farm, owner })
 
@@ -294,7 +333,7 @@ export const StakingProgram = (connection: Connection) => {
 
     const whitelistProof = findWhitelistProofAddress({
       farm,
-      creatorOrMint,
+      creatorOrMint: merkleRoot ?? creatorOrMint,
     })
 
     const farmerVault = await utils.token.associatedAddress({
@@ -309,16 +348,18 @@ export const StakingProgram = (connection: Connection) => {
 
     const stakeReceipt = findStakeReceiptAddress({ farmer, mint })
 
-    const hasEssence = await hasBuffAttribute(connection, mint)
+    const buffProof = findBuffProofAddress({ farm, mint })
 
     const ix = stake(
-      { amount, hasEssence },
+      { amount, merkleProof: merkleProof.map((node) => [...node]) },
       {
         farm,
         farmer,
+        secondaryReward: findSecondaryRewardAddress({ farm }),
 
         gemMint: mint,
         whitelistProof,
+        buffProof,
         farmerVault,
         gemOwnerAta,
 
@@ -356,9 +397,12 @@ export const StakingProgram = (connection: Connection) => {
       owner: authority,
     })
 
+    const secondaryReward = findSecondaryRewardAddress({ farm })
+
     const ix = claimRewards({
       farm,
       farmer,
+      secondaryReward,
       rewardMint: farmData.reward.mint,
       farmRewardVault,
       farmerRewardVault,
@@ -369,6 +413,33 @@ export const StakingProgram = (connection: Connection) => {
       associatedTokenProgram,
     })
 
+    // Farms with a secondary reward pay it from its vault to the farmer's
+    // token account, which must already exist.
+    const secondaryRewardData = await SecondaryReward.fetch(
+      connection,
+      secondaryReward
+    )
+
+    if (secondaryRewardData) {
+      const { mint } = secondaryRewardData.reward
+
+      ix.keys.push(
+        {
+          pubkey: await utils.token.associatedAddress({ mint, owner: farm }),
+          isSigner: false,
+          isWritable: true,
+        },
+        {
+          pubkey: await utils.token.associatedAddress({
+            mint,
+            owner: authority,
+          }),
+          isSigner: false,
+          isWritable: true,
+        }
+      )
+    }
+
     return { ix }
   }
 
@@ -390,6 +461,7 @@ export const StakingProgram = (connection: Connection) => {
     const ix = unstake({
       farm,
       farmer,
+      secondaryReward: findSecondaryRewardAddress({ farm }),
       gemMint: mint,
       stakeReceipt,
       farmerVault,
@@ -404,6 +476,47 @@ export const StakingProgram = (connection: Connection) => {
     return { ix }
   }
 
+  const createPartialUnstakeInstruction = async ({
+    farm,
+    mint,
+    amount,
+    owner,
+  }: IPartialUnstake) => {
+    const farmer = findFarmerAddress({ farm, owner })
+
+    const farmerVault = await utils.token.associatedAddress({
+      mint,
+      owner: farmer,
+    })
+
+    const gemOwnerAta = await utils.token.associatedAddress({
+      mint,
+      owner,
+    })
+
+    const stakeReceipt = findStakeReceiptAddress({ farmer, mint })
+
+    const ix = partialUnstake(
+      { amount },
+      {
+        farm,
+        farmer,
+        secondaryReward: findSecondaryRewardAddress({ farm }),
+        gemMint: mint,
+        stakeReceipt,
+        farmerVault,
+        gemOwnerAta,
+        owner,
+        rent,
+        systemProgram,
+        tokenProgram,
+        associatedTokenProgram,
+      }
+    )
+
+    return { ix }
+  }
+
   const createForceUnstakeInstruction = async ({
     farm,
     farmAuthority,
@@ -424,9 +537,16 @@ export const StakingProgram = (connection: Connection) => {
 
     const stakeReceipt = findStakeReceiptAddress({ farmer, mint })
 
+    const farmManager = findFarmManagerAddress({
+      farm,
+      authority: farmAuthority,
+    })
+
     const ix = forceUnstake({
       farm,
+      farmManager,
       farmer,
+      secondaryReward: findSecondaryRewardAddress({ farm }),
       gemMint: mint,
       stakeReceipt,
       farmerVault,
@@ -453,6 +573,7 @@ export const StakingProgram = (connection: Connection) => {
     createInitializeFarmerInstruction,
     createStakeInstruction,
     createUnstakeInstruction,
+    createPartialUnstakeInstruction,
     createForceUnstakeInstruction,
     createClaimRewardsInstruction,
   }
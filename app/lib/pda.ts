@@ -62,3 +62,25 @@ export const findStakeReceiptAddress = ({
     [Buffer.from("stake_receipt"), farmer.toBuffer(), mint.toBuffer()],
     PROGRAM_ID
   )[0];
+
+export const findBuffProofAddress = ({
+  farm,
+  mint,
+}: {
+  farm: PublicKey;
+  mint: PublicKey;
+}): PublicKey =>
+  utils.publicKey.findProgramAddressSync(
+    [Buffer.from("buff_proof"), farm.toBuffer(), mint.toBuffer()],
+    PROGRAM_ID
+  )[0];
+
+export const findSecondaryRewardAddress = ({
+  farm,
+}: {
+  farm: PublicKey;
+}): PublicKey =>
+  utils.publicKey.findProgramAddressSync(
+    [Buffer.from("secondary_reward"), farm.toBuffer()],
+    PROGRAM_ID
+  )[0];
@@ -1,11 +1,15 @@
-use crate::client::StakingClient;
+use crate::client::{merkle, StakingClient, WhitelistConfig};
 use anchor_client::{
-    solana_sdk::{pubkey::Pubkey, signature::read_keypair_file},
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{read_keypair_file, Signature},
+    },
     Cluster,
 };
-use anyhow::{anyhow, bail, Result};
+use anyhow::{anyhow, Result};
 use clap::{arg_enum, Parser, Subcommand};
-use std::{path::PathBuf, rc::Rc};
+use dskullys_staking::state::{FarmManager, FarmState};
+use std::{collections::HashMap, path::PathBuf, rc::Rc};
 
 mod output;
 use output::*;
@@ -32,6 +36,15 @@ struct Args {
     #[clap(short, long, global = true, name = "PATH")]
     /// Path to the farm authority keypair file.
     keypair: Option<PathBuf>,
+    #[clap(
+        short,
+        long,
+        global = true,
+        value_name = "FORMAT",
+        default_value = "table"
+    )]
+    /// Output format. Possible values: ["table", "json", "csv"].
+    output: OutputFormat,
     #[clap(subcommand)]
     command: Command,
 }
@@ -40,6 +53,103 @@ struct Args {
 enum Command {
     #[clap(subcommand)]
     Farm(FarmCommand),
+
+    #[clap(subcommand)]
+    Farmer(FarmerCommand),
+
+    /// Decodes the staking events emitted by a transaction.
+    Events { signature: Signature },
+}
+
+#[derive(Debug, Subcommand)]
+/// Staking commands for the payer's own gems.
+enum FarmerCommand {
+    /// Creates the payer's farmer account, needed before staking.
+    #[clap(alias = "new", alias = "create")]
+    Init {
+        /// Farm address.
+        farm_address: Pubkey,
+    },
+
+    /// Stakes a whitelisted gem.
+    Stake {
+        /// Farm address.
+        farm_address: Pubkey,
+        /// Mint of the gem to stake.
+        gem_mint: Pubkey,
+        /// How many tokens to stake, for fungible gems. Adds to the running
+        /// stake if there is one.
+        #[clap(long, default_value = "1")]
+        amount: u64,
+        /// Proofs file written by `farm wl add-merkle`, for gems whitelisted
+        /// through a merkle root.
+        #[clap(short, long)]
+        proofs: Option<PathBuf>,
+    },
+
+    /// Unstakes a gem. Gems with a cooldown must be unstaked twice: once to
+    /// begin the cooldown and again once it is over. Gems of a closed farm
+    /// are withdrawn right away, without their rewards.
+    Unstake {
+        /// Farm address.
+        farm_address: Pubkey,
+        /// Mint of the staked gem.
+        gem_mint: Pubkey,
+        /// Only unstake part of a fungible stake, the rest keeps earning.
+        #[clap(long)]
+        amount: Option<u64>,
+        /// Also claim the accrued rewards once the gems are withdrawn.
+        #[clap(long, conflicts_with = "amount")]
+        claim: bool,
+        /// Close the stake receipt once the gems are withdrawn, returning its
+        /// rent.
+        #[clap(long, conflicts_with = "amount")]
+        close: bool,
+    },
+
+    /// Stakes many NFTs, packing them in as few transactions as possible.
+    StakeMany {
+        /// Farm address.
+        farm_address: Pubkey,
+        /// File with the mints to stake, one address per line.
+        mints: PathBuf,
+        /// Proofs file written by `farm wl add-merkle`, for gems whitelisted
+        /// through a merkle root.
+        #[clap(short, long)]
+        proofs: Option<PathBuf>,
+    },
+
+    /// Unstakes many gems, packing them in as few transactions as possible.
+    UnstakeMany {
+        /// Farm address.
+        farm_address: Pubkey,
+        /// File with the mints to unstake, one address per line. Defaults to
+        /// every gem that can be unstaked right now.
+        mints: Option<PathBuf>,
+    },
+
+    /// Closes the stake receipts of unstaked gems, returning their rent.
+    ReclaimRent {
+        /// Farm address.
+        farm_address: Pubkey,
+        /// Also close the farmer account, once nothing is staked nor owed.
+        #[clap(long)]
+        close_farmer: bool,
+    },
+
+    /// Claims the accrued rewards.
+    Claim {
+        /// Farm address.
+        farm_address: Pubkey,
+    },
+
+    /// Shows a farmer and their stakes.
+    Show {
+        /// Farm address.
+        farm_address: Pubkey,
+        /// Farmer's wallet address, the payer by default.
+        owner: Option<Pubkey>,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -49,9 +159,12 @@ enum FarmCommand {
     #[clap(alias = "ls")]
     List { manager_address: Option<Pubkey> },
 
-    /// Shows farm details.
+    /// Shows the farm's rewards, stakes and how long the rewards will last.
     Stats { farm: Pubkey },
 
+    /// Compares the farm's reward accounting against its reward vault balance.
+    Audit { farm: Pubkey },
+
     /// Creates a new farm.
     #[clap(alias = "new", alias = "init")]
     Create {
@@ -59,12 +172,63 @@ enum FarmCommand {
         reward_mint: Pubkey,
     },
 
+    /// Pauses staking, rewards or the whole farm. Unstaking always works.
+    Pause {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+        /// Possible values: ["Staking", "Rewards", "All"].
+        #[clap(value_name = "WHAT", default_value = "Staking")]
+        what: PauseTarget,
+    },
+
+    /// Lifts any pause on the farm.
+    Resume {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+    },
+
+    /// Removes the farm's whitelists, buffs and managers, then closes it and
+    /// sends the rewards left to the authority. Nothing can be staked.
+    Close {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+    },
+
+    /// Moves the farm's accounts created by older versions of the program to
+    /// their current layout. Anyone can run it, the payer covers the extra rent.
+    /// The farm authority also finishes migrating the older stakes.
+    Upgrade {
+        /// Farm address.
+        farm_address: Pubkey,
+    },
+
+    /// Sets what happens when farmers accrue more rewards than the farm has.
+    Policy {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+        /// Possible values: ["Fail", "Partial", "Skip"].
+        #[clap(value_name = "POLICY")]
+        policy: AccrualPolicy,
+    },
+
+    /// Set or extend the period in which the farm emits rewards.
+    Schedule {
+        #[clap(subcommand)]
+        action: ScheduleAction,
+    },
+
     /// Add or remove farm managers.
     Manager {
         #[clap(subcommand)]
         action: ManagerAction,
     },
 
+    /// Transfer the farm authority to another wallet.
+    Authority {
+        #[clap(subcommand)]
+        action: AuthorityAction,
+    },
+
     /// Add, remove and list farm whitelists.
     #[clap(alias = "wl")]
     Whitelist {
@@ -72,28 +236,75 @@ enum FarmCommand {
         action: WhitelistAction,
     },
 
+    /// Add, remove and list reward buffs (e.g. the Essence trait).
+    Buff {
+        #[clap(subcommand)]
+        action: BuffAction,
+    },
+
     /// Deposit or withdraw funds from a farm's reward vault.
     Reward {
         #[clap(subcommand)]
         action: RewardAction,
     },
+
+    /// Unstakes a gem on behalf of its owner, settling their rewards.
+    ForceUnstake {
+        /// Farm address (payer must own a farm manager account).
+        farm_address: Pubkey,
+        /// Wallet address of the gem owner.
+        owner: Pubkey,
+        /// Mint of the staked gem.
+        gem_mint: Pubkey,
+    },
 }
 
 #[derive(Debug, Subcommand)]
 enum WhitelistAction {
-    /// Add a new creator or spl-token to the farm whitelist.
+    /// Add a new creator, spl-token or verified collection to the farm whitelist.
     #[clap(alias = "create", alias = "new")]
     Add {
         /// Farm address (payer must own a farm manager account).
         farm_address: Pubkey,
         /// Address to add to the whitelist.
         address: Pubkey,
-        /// Whitelist type. Possible values: ["Creator", "SplToken"].
+        /// Whitelist type. Possible values: ["Creator", "SplToken", "Collection"].
         #[clap(value_name = "WHITELIST-TYPE", default_value = "Creator")]
         ty: WhitelistType,
+        #[clap(flatten)]
+        config: WhitelistArgs,
+    },
+
+    /// Whitelist a set of mints through their merkle root.
+    AddMerkle {
+        /// Farm address (payer must own a farm manager account).
+        farm_address: Pubkey,
+        /// File with one mint address per line.
+        mints_file: PathBuf,
+        #[clap(flatten)]
+        config: WhitelistArgs,
+        /// Where to write the proof of every mint, as a JSON map of mint to proof.
+        #[clap(short, long)]
+        proofs: Option<PathBuf>,
+    },
+
+    /// Change the reward rate of a whitelisted address.
+    Update {
+        /// Farm address (payer must own a farm manager account).
+        farm_address: Pubkey,
+        /// Whitelisted address.
+        address: Pubkey,
         /// How many tokens/sec the farmers will receive for every token they stake.
-        #[clap(default_value = "1")]
         reward_rate: u64,
+        /// New secondary reward rate, unchanged by default.
+        #[clap(long)]
+        secondary_reward_rate: Option<u64>,
+        /// Also move the gems that are already staked to the new rate.
+        #[clap(long)]
+        migrate: bool,
+        /// Proofs of the staked mints, needed to migrate merkle root whitelists.
+        #[clap(short, long)]
+        proofs: Option<PathBuf>,
     },
 
     /// Remove a creator or spl-token from the farm whitelist.
@@ -112,6 +323,90 @@ enum WhitelistAction {
     },
 }
 
+#[derive(Debug, clap::Args)]
+struct WhitelistArgs {
+    /// How many tokens/sec the farmers will receive for every token they stake.
+    #[clap(default_value = "1")]
+    reward_rate: u64,
+    /// Secondary reward tokens/sec the farmers will receive for every token they
+    /// stake, for farms with a secondary reward.
+    #[clap(long, default_value = "0")]
+    secondary_reward_rate: u64,
+    /// Seconds a gem must stay staked before it can be unstaked.
+    #[clap(long, default_value = "0")]
+    min_stake_duration: u64,
+    /// Seconds the farmers must wait between beginning and finishing an unstake.
+    #[clap(long, default_value = "0")]
+    unstake_cooldown: u32,
+}
+
+impl From<WhitelistArgs> for WhitelistConfig {
+    fn from(args: WhitelistArgs) -> Self {
+        Self {
+            reward_rate: args.reward_rate,
+            secondary_reward_rate: args.secondary_reward_rate,
+            min_stake_duration: args.min_stake_duration,
+            unstake_cooldown: args.unstake_cooldown,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum BuffAction {
+    /// Buff the reward rate of the given mints.
+    #[clap(alias = "create", alias = "new")]
+    Add {
+        /// Farm address (payer must own a farm manager account).
+        farm_address: Pubkey,
+        /// Reward rate bonus, in percent (100 doubles the reward rate).
+        #[clap(short, long, default_value = "100")]
+        factor: u64,
+        /// Mints to buff.
+        #[clap(required = true)]
+        mints: Vec<Pubkey>,
+    },
+
+    /// Remove the buff from the given mints.
+    Remove {
+        /// Farm address (payer must own a farm manager account).
+        farm_address: Pubkey,
+        /// Mints to remove the buff from.
+        #[clap(required = true)]
+        mints: Vec<Pubkey>,
+    },
+
+    /// Displays every buffed mint in a given farm.
+    #[clap(alias = "ls")]
+    List {
+        /// Farm address.
+        farm_address: Pubkey,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ScheduleAction {
+    /// Set the reward window. Its start can't change once it has started, and
+    /// its end can't be in the past.
+    Set {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+        /// Unix timestamp when rewards start, unchanged by default.
+        #[clap(long)]
+        start: Option<u64>,
+        /// Unix timestamp when rewards end (0 for no end).
+        #[clap(long, default_value = "0")]
+        end: u64,
+    },
+
+    /// Push back the end of a running reward window.
+    Extend {
+        /// Farm address (payer must own a farm manager account).
+        farm_address: Pubkey,
+        /// New unix timestamp when rewards end.
+        end: u64,
+    },
+}
+
 #[derive(Debug, Subcommand)]
 enum RewardAction {
     /// Fund farm rewards.
@@ -120,14 +415,28 @@ enum RewardAction {
         farm_address: Pubkey,
         /// Amount to deposit (without decimals).
         amount: u64,
+        /// Fund the secondary reward instead.
+        #[clap(long)]
+        secondary: bool,
     },
 
-    /// Withdraw farm rewards.
+    /// Withdraw unreserved farm rewards.
     Withdraw {
-        /// Farm address (payer must own a farm manager account).
+        /// Farm address (payer must be the farm authority).
         farm_address: Pubkey,
-        /// Amount to deposit (without decimals).
+        /// Amount to withdraw (without decimals).
         amount: u64,
+        /// Withdraw the secondary reward instead.
+        #[clap(long)]
+        secondary: bool,
+    },
+
+    /// Pay a second token on top of the farm's reward.
+    AddSecondary {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+        /// Mint of the secondary reward.
+        reward_mint: Pubkey,
     },
 }
 
@@ -140,6 +449,18 @@ enum ManagerAction {
         farm_address: Pubkey,
         /// New manager's wallet address.
         manager_owner: Option<Pubkey>,
+        /// Comma separated roles granted to the manager, all of them by default.
+        /// Possible values: ["Whitelist", "Fund", "ForceUnstake", "Rates"].
+        #[clap(long, value_name = "ROLES", value_delimiter = ',')]
+        roles: Vec<ManagerRole>,
+    },
+    /// Remove a farm manager.
+    #[clap(alias = "rm")]
+    Remove {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+        /// Manager's wallet address.
+        manager_owner: Pubkey,
     },
     /// List all the managers from a farm.
     #[clap(alias = "ls")]
@@ -149,11 +470,102 @@ enum ManagerAction {
     },
 }
 
+#[derive(Debug, Subcommand)]
+enum AuthorityAction {
+    /// Propose a new farm authority, which must accept it.
+    Transfer {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+        /// New authority's wallet address.
+        new_authority: Pubkey,
+    },
+    /// Accept a proposed farm authority transfer. The previous authority's
+    /// farm manager is removed.
+    Accept {
+        /// Farm address (payer must be the proposed authority).
+        farm_address: Pubkey,
+    },
+    /// Cancel a pending farm authority transfer.
+    Cancel {
+        /// Farm address (payer must be the farm authority).
+        farm_address: Pubkey,
+    },
+}
+
 arg_enum! {
     #[derive(Debug)]
     enum WhitelistType {
         Creator,
         SplToken,
+        Collection,
+    }
+}
+
+arg_enum! {
+    #[derive(Debug)]
+    enum AccrualPolicy {
+        Fail,
+        Partial,
+        Skip,
+    }
+}
+
+arg_enum! {
+    #[derive(Debug)]
+    enum PauseTarget {
+        Staking,
+        Rewards,
+        All,
+    }
+}
+
+impl From<PauseTarget> for FarmState {
+    fn from(target: PauseTarget) -> Self {
+        match target {
+            PauseTarget::Staking => FarmState::StakingPaused,
+            PauseTarget::Rewards => FarmState::RewardsPaused,
+            PauseTarget::All => FarmState::Frozen,
+        }
+    }
+}
+
+arg_enum! {
+    #[derive(Debug, Clone, Copy)]
+    enum ManagerRole {
+        Whitelist,
+        Fund,
+        ForceUnstake,
+        Rates,
+    }
+}
+
+impl ManagerRole {
+    fn permissions(roles: &[ManagerRole]) -> u8 {
+        if roles.is_empty() {
+            return FarmManager::ALL;
+        }
+
+        roles.iter().fold(0, |permissions, role| {
+            permissions
+                | match role {
+                    ManagerRole::Whitelist => FarmManager::WHITELIST,
+                    ManagerRole::Fund => FarmManager::FUND,
+                    ManagerRole::ForceUnstake => FarmManager::FORCE_UNSTAKE,
+                    ManagerRole::Rates => FarmManager::RATES,
+                }
+        })
+    }
+}
+
+impl From<AccrualPolicy> for dskullys_staking::state::AccrualPolicy {
+    fn from(policy: AccrualPolicy) -> Self {
+        match policy {
+            AccrualPolicy::Fail => dskullys_staking::state::AccrualPolicy::Fail,
+            AccrualPolicy::Partial => {
+                dskullys_staking::state::AccrualPolicy::Partial
+            }
+            AccrualPolicy::Skip => dskullys_staking::state::AccrualPolicy::Skip,
+        }
     }
 }
 
@@ -166,6 +578,9 @@ impl From<WhitelistType> for dskullys_staking::state::WhitelistType {
             WhitelistType::SplToken => {
                 dskullys_staking::state::WhitelistType::Mint
             }
+            WhitelistType::Collection => {
+                dskullys_staking::state::WhitelistType::Collection
+            }
         }
     }
 }
@@ -193,7 +608,7 @@ pub fn run() -> Result<()> {
 
     let client = StakingClient::new(args.url, Rc::new(payer))?;
 
-    process_command(client, args.command, OutputOptions::default())
+    process_command(client, args.command, OutputOptions::new(args.output))
 }
 
 fn process_command(
@@ -211,22 +626,121 @@ fn process_command(
                 FarmListOutput(client.get_manager_farms(manager_address)?),
                 options,
             ),
-            // TODO
-            FarmCommand::Stats { farm } => client.farm_stats(farm),
+            FarmCommand::Stats { farm } => {
+                output_command(FarmStatsOutput(client.farm_stats(farm)?), options)
+            }
+
+            FarmCommand::Pause { farm_address, what } => {
+                client.set_farm_state(farm_address, what.into())
+            }
+
+            FarmCommand::Resume { farm_address } => {
+                client.set_farm_state(farm_address, FarmState::Active)
+            }
+
+            FarmCommand::Close { farm_address } => client.close_farm(farm_address),
+
+            FarmCommand::Upgrade { farm_address } => {
+                client.upgrade_farm(farm_address)
+            }
+
+            FarmCommand::Policy {
+                farm_address,
+                policy,
+            } => client.set_accrual_policy(farm_address, policy.into()),
+
+            FarmCommand::Schedule { action } => match action {
+                ScheduleAction::Set {
+                    farm_address,
+                    start,
+                    end,
+                } => client.set_reward_window(farm_address, start, end),
+
+                ScheduleAction::Extend { farm_address, end } => {
+                    client.extend_reward_window(farm_address, end)
+                }
+            },
+
+            FarmCommand::Audit { farm } => {
+                let farm_account = client.get_farm(farm)?;
+                let vault_balance =
+                    client.get_reward_vault_balance(farm, &farm_account)?;
+
+                output_command(
+                    FarmAuditOutput {
+                        farm,
+                        reward: farm_account.reward,
+                        vault_balance,
+                    },
+                    options,
+                )
+            }
 
             FarmCommand::Whitelist { action } => match action {
                 WhitelistAction::Add {
                     farm_address,
                     address,
                     ty,
-                    reward_rate,
+                    config,
                 } => client.add_to_whitelist(
                     farm_address,
                     address,
                     ty.into(),
-                    reward_rate,
+                    config.into(),
                 ),
 
+                WhitelistAction::AddMerkle {
+                    farm_address,
+                    mints_file,
+                    config,
+                    proofs,
+                } => {
+                    let mints = merkle::read_mints(mints_file)?;
+                    let tree = client.add_merkle_whitelist(
+                        farm_address,
+                        &mints,
+                        config.into(),
+                    )?;
+
+                    if let Some(path) = proofs {
+                        let file = std::fs::File::create(path)?;
+                        serde_json::to_writer_pretty(file, &tree.proofs(&mints))?;
+                    }
+
+                    Ok(())
+                }
+
+                WhitelistAction::Update {
+                    farm_address,
+                    address,
+                    reward_rate,
+                    secondary_reward_rate,
+                    migrate,
+                    proofs,
+                } => {
+                    client.update_whitelist(
+                        farm_address,
+                        address,
+                        reward_rate,
+                        secondary_reward_rate,
+                    )?;
+
+                    if migrate {
+                        let proofs = match proofs {
+                            Some(path) => merkle::read_proofs(path)?,
+                            None => Default::default(),
+                        };
+                        let migrated = client.migrate_whitelist(
+                            farm_address,
+                            address,
+                            &proofs,
+                        )?;
+                        eprintln!("Migrated {} stakes to the new rate.", migrated);
+                    }
+
+                    Ok(())
+                }
+
                 WhitelistAction::Remove {
                     farm_address,
                     address,
@@ -240,23 +754,80 @@ fn process_command(
                 ),
             },
 
+            FarmCommand::Buff { action } => match action {
+                BuffAction::Add {
+                    farm_address,
+                    factor,
+                    mints,
+                } => mints.into_iter().try_for_each(|mint| {
+                    client.add_buff(farm_address, mint, factor)
+                }),
+
+                BuffAction::Remove {
+                    farm_address,
+                    mints,
+                } => mints
+                    .into_iter()
+                    .try_for_each(|mint| client.remove_buff(farm_address, mint)),
+
+                BuffAction::List { farm_address } => output_command(
+                    BuffListOutput(client.get_farm_buffs(farm_address)?),
+                    options,
+                ),
+            },
+
             FarmCommand::Reward { action } => match action {
                 RewardAction::Deposit {
                     farm_address,
                     amount,
-                } => client.deposit_reward(farm_address, amount),
+                    secondary,
+                } => {
+                    if secondary {
+                        client.deposit_secondary_reward(farm_address, amount)
+                    } else {
+                        client.deposit_reward(farm_address, amount)
+                    }
+                }
 
-                // TODO
-                RewardAction::Withdraw { .. } => {
-                    bail!("Withdrawal not supported yet.")
+                RewardAction::Withdraw {
+                    farm_address,
+                    amount,
+                    secondary,
+                } => {
+                    if secondary {
+                        client.withdraw_secondary_reward(farm_address, amount)
+                    } else {
+                        client.withdraw_reward(farm_address, amount)
+                    }
                 }
+
+                RewardAction::AddSecondary {
+                    farm_address,
+                    reward_mint,
+                } => client.add_secondary_reward(farm_address, reward_mint),
             },
 
+            FarmCommand::ForceUnstake {
+                farm_address,
+                owner,
+                gem_mint,
+            } => client.force_unstake(farm_address, owner, gem_mint),
+
             FarmCommand::Manager { action } => match action {
                 ManagerAction::Add {
                     farm_address,
                     manager_owner,
-                } => client.add_manager(farm_address, manager_owner),
+                    roles,
+                } => client.add_manager(
+                    farm_address,
+                    manager_owner,
+                    ManagerRole::permissions(&roles),
+                ),
+
+                ManagerAction::Remove {
+                    farm_address,
+                    manager_owner,
+                } => client.remove_manager(farm_address, manager_owner),
 
                 ManagerAction::List { farm_address } => output_command(
                     FarmManagerListOutput(
@@ -265,6 +836,125 @@ fn process_command(
                     options,
                 ),
             },
+
+            FarmCommand::Authority { action } => match action {
+                AuthorityAction::Transfer {
+                    farm_address,
+                    new_authority,
+                } => client.propose_authority(farm_address, new_authority),
+
+                AuthorityAction::Accept { farm_address } => {
+                    client.accept_authority(farm_address)
+                }
+
+                AuthorityAction::Cancel { farm_address } => {
+                    client.cancel_authority_transfer(farm_address)
+                }
+            },
+        },
+
+        Command::Farmer(cmd) => match cmd {
+            FarmerCommand::Init { farm_address } => {
+                client.initialize_farmer(farm_address)
+            }
+
+            FarmerCommand::Stake {
+                farm_address,
+                gem_mint,
+                amount,
+                proofs,
+            } => {
+                let merkle_proof = match proofs {
+                    Some(path) => merkle::read_proofs(path)?
+                        .remove(&gem_mint)
+                        .ok_or_else(|| {
+                            anyhow!("No proof found for {}.", gem_mint)
+                        })?,
+                    None => vec![],
+                };
+
+                client.stake(farm_address, gem_mint, amount, merkle_proof)
+            }
+
+            FarmerCommand::Unstake {
+                farm_address,
+                gem_mint,
+                amount,
+                claim,
+                close,
+            } => match amount {
+                Some(amount) => {
+                    client.partial_unstake(farm_address, gem_mint, amount)
+                }
+                None => client.unstake(farm_address, gem_mint, claim, close),
+            },
+
+            FarmerCommand::StakeMany {
+                farm_address,
+                mints,
+                proofs,
+            } => {
+                let merkle_proofs = match proofs {
+                    Some(path) => merkle::read_proofs(path)?,
+                    None => HashMap::new(),
+                };
+
+                client.stake_many(
+                    farm_address,
+                    &merkle::read_mints(mints)?,
+                    &merkle_proofs,
+                )
+            }
+
+            FarmerCommand::UnstakeMany {
+                farm_address,
+                mints,
+            } => {
+                let gem_mints = match mints {
+                    Some(path) => merkle::read_mints(path)?,
+                    None => vec![],
+                };
+
+                client.unstake_many(farm_address, &gem_mints)
+            }
+
+            FarmerCommand::ReclaimRent {
+                farm_address,
+                close_farmer,
+            } => client.reclaim_rent(farm_address, close_farmer),
+
+            FarmerCommand::Claim { farm_address } => {
+                client.claim_rewards(farm_address)
+            }
+
+            FarmerCommand::Show {
+                farm_address,
+                owner,
+            } => {
+                let owner = owner.unwrap_or_else(|| client.payer());
+                let (farmer, farmer_account) =
+                    client.get_farmer(farm_address, owner)?;
+
+                output_command(
+                    FarmerShowOutput {
+                        farmer,
+                        owner,
+                        // Fails when the farm can't pay the rewards out.
+                        pending_rewards: client.pending_rewards(farmer).ok(),
+                        pending_secondary_rewards: client
+                            .pending_secondary_rewards(farmer)
+                            .ok(),
+                        reward_shortfall: farmer_account.reward_shortfall(),
+                        receipts: client.get_farmer_stake_receipts(farmer)?,
+                    },
+                    options,
+                )
+            }
         },
+
+        Command::Events { signature } => output_command(
+            EventListOutput(client.get_transaction_events(&signature)?),
+            options,
+        ),
     }
 }
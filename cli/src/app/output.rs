@@ -1,37 +1,161 @@
 use std::{fmt::Display, io::Write};
 
+use crate::client::{events::StakingEvent, FarmStats};
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use anyhow::Result;
+use clap::arg_enum;
 use dskullys_staking::state::{
-    Farm, FarmManager, WhitelistProof, WhitelistType,
+    BuffProof, Farm, FarmManager, Reward, StakeReceipt, WhitelistProof,
+    WhitelistType,
 };
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+
+arg_enum! {
+    #[derive(Debug, Clone, Copy)]
+    pub enum OutputFormat {
+        Table,
+        Json,
+        Csv,
+    }
+}
 
 pub(crate) struct OutputOptions {
     writer: Box<dyn Write>,
+    format: OutputFormat,
+}
+
+impl OutputOptions {
+    pub fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for OutputOptions {
     fn default() -> Self {
         Self {
             writer: Box::new(std::io::stdout()),
+            format: OutputFormat::Table,
         }
     }
 }
 
-pub(crate) trait Output: Display {}
+pub(crate) trait Output: Display + Serialize {}
 
 pub(crate) fn output_command<T: Output>(
     output: T,
     mut options: OutputOptions,
 ) -> Result<()> {
-    writeln!(options.writer, "{}", output)?;
+    match options.format {
+        OutputFormat::Table => writeln!(options.writer, "{}", output)?,
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut options.writer, &output)?;
+            writeln!(options.writer)?;
+        }
+        OutputFormat::Csv => {
+            write_csv(&mut options.writer, serde_json::to_value(&output)?)?
+        }
+    }
     Ok(())
 }
 
+/// Writes a list of objects as CSV rows, with their keys as the header. A
+/// single object is written as a single row, and nested values are written as
+/// JSON.
+fn write_csv(writer: &mut dyn Write, value: Value) -> Result<()> {
+    let rows = match value {
+        Value::Array(rows) => rows,
+        value => vec![value],
+    };
+
+    let header: Vec<String> = match rows.first() {
+        Some(Value::Object(row)) => row.keys().cloned().collect(),
+        Some(_) => vec!["value".to_string()],
+        None => return Ok(()),
+    };
+
+    writeln!(writer, "{}", csv_line(header.iter().map(String::as_str)))?;
+
+    for row in &rows {
+        let cells: Vec<String> = match row {
+            Value::Object(row) => header
+                .iter()
+                .map(|key| csv_cell(row.get(key).unwrap_or(&Value::Null)))
+                .collect(),
+            value => vec![csv_cell(value)],
+        };
+
+        writeln!(writer, "{}", csv_line(cells.iter().map(String::as_str)))?;
+    }
+
+    Ok(())
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+fn csv_line<'a>(cells: impl Iterator<Item = &'a str>) -> String {
+    cells
+        .map(|cell| {
+            if cell.contains(&[',', '"', '\n', '\r'][..]) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Serializes pubkeys and other displayable values as strings.
+fn as_string<T: Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
 pub(crate) struct FarmListOutput(pub Vec<(Pubkey, Farm)>);
 pub(crate) struct FarmCreateOutput(pub Pubkey);
 pub(crate) struct FarmManagerListOutput(pub Vec<(Pubkey, FarmManager)>);
 pub(crate) struct WhitelistListOutput(pub Vec<(Pubkey, WhitelistProof)>);
+pub(crate) struct BuffListOutput(pub Vec<(Pubkey, BuffProof)>);
+pub(crate) struct EventListOutput(pub Vec<StakingEvent>);
+pub(crate) struct FarmStatsOutput(pub FarmStats);
+pub(crate) struct FarmerShowOutput {
+    pub farmer: Pubkey,
+    pub owner: Pubkey,
+    pub pending_rewards: Option<u64>,
+    // Only set for farms with a secondary reward that can pay it out.
+    pub pending_secondary_rewards: Option<u64>,
+    pub reward_shortfall: u64,
+    pub receipts: Vec<(Pubkey, StakeReceipt)>,
+}
+pub(crate) struct FarmAuditOutput {
+    pub farm: Pubkey,
+    pub reward: Reward,
+    pub vault_balance: u64,
+}
+
+impl Serialize for FarmCreateOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(serialize_with = "as_string")]
+            farm: &'a Pubkey,
+        }
+
+        Row { farm: &self.0 }.serialize(serializer)
+    }
+}
 
 impl Output for FarmCreateOutput {}
 impl Display for FarmCreateOutput {
@@ -40,6 +164,26 @@ impl Display for FarmCreateOutput {
     }
 }
 
+impl Serialize for FarmListOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(serialize_with = "as_string")]
+            address: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            authority: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            reward_mint: &'a Pubkey,
+        }
+
+        serializer.collect_seq(self.0.iter().map(|(k, farm)| Row {
+            address: k,
+            authority: &farm.authority,
+            reward_mint: &farm.reward.mint,
+        }))
+    }
+}
+
 impl Output for FarmListOutput {}
 impl Display for FarmListOutput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -51,35 +195,448 @@ impl Display for FarmListOutput {
     }
 }
 
+impl Serialize for FarmManagerListOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(serialize_with = "as_string")]
+            address: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            owner: &'a Pubkey,
+            roles: String,
+        }
+
+        serializer.collect_seq(self.0.iter().map(|(k, fm)| Row {
+            address: k,
+            owner: &fm.authority,
+            roles: manager_roles(fm),
+        }))
+    }
+}
+
 impl Output for FarmManagerListOutput {}
 impl Display for FarmManagerListOutput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "{:^44} | {:^44}", "Address", "Owner")?;
+        writeln!(f, "{:^44} | {:^44} | {:^36}", "Address", "Owner", "Roles")?;
         for (k, fm) in &self.0 {
-            writeln!(f, "{:^44} | {:^44}", k, fm.authority)?;
+            writeln!(
+                f,
+                "{:^44} | {:^44} | {:^36}",
+                k,
+                fm.authority,
+                manager_roles(fm)
+            )?;
         }
         Ok(())
     }
 }
 
+fn manager_roles(manager: &FarmManager) -> String {
+    [
+        (FarmManager::WHITELIST, "Whitelist"),
+        (FarmManager::FUND, "Fund"),
+        (FarmManager::FORCE_UNSTAKE, "ForceUnstake"),
+        (FarmManager::RATES, "Rates"),
+    ]
+    .iter()
+    .filter(|(permission, _)| manager.can(*permission))
+    .map(|(_, name)| *name)
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+impl Serialize for WhitelistListOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(serialize_with = "as_string")]
+            address: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            whitelisted_address: &'a Pubkey,
+            reward_rate: u64,
+            secondary_reward_rate: u64,
+            rate_epoch: u32,
+            min_stake_duration: u64,
+            unstake_cooldown: u32,
+            #[serde(rename = "type")]
+            ty: &'static str,
+        }
+
+        serializer.collect_seq(self.0.iter().map(|(k, wl)| Row {
+            address: k,
+            whitelisted_address: &wl.whitelisted_address,
+            reward_rate: wl.reward_rate,
+            secondary_reward_rate: wl.secondary_reward_rate,
+            rate_epoch: wl.rate_epoch,
+            min_stake_duration: wl.min_stake_duration,
+            unstake_cooldown: wl.unstake_cooldown,
+            ty: whitelist_type_name(wl.ty),
+        }))
+    }
+}
+
 impl Output for WhitelistListOutput {}
 impl Display for WhitelistListOutput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(
             f,
-            "{:^44} | {:^22} | {:^22}",
-            "Address", "Reward rate", "Type"
+            "{:^44} | {:^22} | {:^22} | {:^10} | {:^22} | {:^22} | {:^22}",
+            "Address",
+            "Reward rate",
+            "Secondary rate",
+            "Epoch",
+            "Min. stake (s)",
+            "Cooldown (s)",
+            "Type"
         )?;
         for (k, wl) in &self.0 {
             writeln!(
                 f,
-                "{:^44} | {:^22} | {:^22}",
+                "{:^44} | {:^22} | {:^22} | {:^10} | {:^22} | {:^22} | {:^22}",
                 k,
                 wl.reward_rate,
-                match wl.ty {
-                    WhitelistType::Creator => "Creator",
-                    WhitelistType::Mint => "Mint",
-                }
+                wl.secondary_reward_rate,
+                wl.rate_epoch,
+                wl.min_stake_duration,
+                wl.unstake_cooldown,
+                whitelist_type_name(wl.ty)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn whitelist_type_name(ty: WhitelistType) -> &'static str {
+    match ty {
+        WhitelistType::Creator => "Creator",
+        WhitelistType::Mint => "Mint",
+        WhitelistType::MerkleRoot => "Merkle root",
+        WhitelistType::Collection => "Collection",
+    }
+}
+
+impl Serialize for BuffListOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(serialize_with = "as_string")]
+            address: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            mint: &'a Pubkey,
+            factor: u64,
+        }
+
+        serializer.collect_seq(self.0.iter().map(|(k, buff)| Row {
+            address: k,
+            mint: &buff.mint,
+            factor: buff.factor,
+        }))
+    }
+}
+
+impl Output for BuffListOutput {}
+impl Display for BuffListOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:^44} | {:^44} | {:^22}", "Address", "Mint", "Factor")?;
+        for (k, buff) in &self.0 {
+            writeln!(
+                f,
+                "{:^44} | {:^44} | {:^22}",
+                k,
+                buff.mint,
+                format!("{}%", buff.factor)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FarmAuditOutput {
+    fn total(&self) -> i128 {
+        self.reward.available as i128 + self.reward.reserved as i128
+    }
+
+    fn difference(&self) -> i128 {
+        self.vault_balance as i128 - self.total()
+    }
+}
+
+impl Serialize for FarmAuditOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(serialize_with = "as_string")]
+            farm: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            reward_mint: &'a Pubkey,
+            available: u64,
+            reserved: u64,
+            total: i128,
+            vault_balance: u64,
+            difference: i128,
+        }
+
+        Row {
+            farm: &self.farm,
+            reward_mint: &self.reward.mint,
+            available: self.reward.available,
+            reserved: self.reward.reserved,
+            total: self.total(),
+            vault_balance: self.vault_balance,
+            difference: self.difference(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Output for FarmAuditOutput {}
+impl Display for FarmAuditOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total = self.total();
+        let difference = self.difference();
+
+        writeln!(f, "Farm:          {}", self.farm)?;
+        writeln!(f, "Reward mint:   {}", self.reward.mint)?;
+        writeln!(f, "Available:     {}", self.reward.available)?;
+        writeln!(f, "Reserved:      {}", self.reward.reserved)?;
+        writeln!(f, "Total:         {}", total)?;
+        writeln!(f, "Vault balance: {}", self.vault_balance)?;
+
+        if difference == 0 {
+            writeln!(f, "OK: reward accounting matches the vault balance.")
+        } else {
+            writeln!(
+                f,
+                "MISMATCH: vault holds {:+} tokens compared to the reward accounting.",
+                difference
+            )
+        }
+    }
+}
+
+impl Serialize for FarmStatsOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct WhitelistRow<'a> {
+            #[serde(serialize_with = "as_string")]
+            whitelisted_address: &'a Pubkey,
+            #[serde(rename = "type")]
+            ty: &'static str,
+            staked: u64,
+        }
+
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(serialize_with = "as_string")]
+            farm: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            reward_mint: &'a Pubkey,
+            state: String,
+            vault_balance: u64,
+            available: u64,
+            reserved: u64,
+            farmers: usize,
+            active_stakes: usize,
+            emission_rate: u64,
+            runway_days: Option<f64>,
+            staked_per_whitelist: Vec<WhitelistRow<'a>>,
+            unattributed_staked: u64,
+        }
+
+        let stats = &self.0;
+
+        Row {
+            farm: &stats.farm,
+            reward_mint: &stats.reward_mint,
+            state: format!("{:?}", stats.state),
+            vault_balance: stats.vault_balance,
+            available: stats.available,
+            reserved: stats.reserved,
+            farmers: stats.farmers,
+            active_stakes: stats.active_stakes,
+            emission_rate: stats.emission_rate,
+            runway_days: stats.runway_days(),
+            staked_per_whitelist: stats
+                .staked_per_whitelist
+                .iter()
+                .map(|(address, ty, staked)| WhitelistRow {
+                    whitelisted_address: address,
+                    ty: whitelist_type_name(*ty),
+                    staked: *staked,
+                })
+                .collect(),
+            unattributed_staked: stats.unattributed_staked,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Output for FarmStatsOutput {}
+impl Display for FarmStatsOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let stats = &self.0;
+
+        writeln!(f, "Farm:            {}", stats.farm)?;
+        writeln!(f, "Reward mint:     {}", stats.reward_mint)?;
+        writeln!(f, "State:           {:?}", stats.state)?;
+        writeln!(f, "Vault balance:   {}", stats.vault_balance)?;
+        writeln!(f, "Available:       {}", stats.available)?;
+        writeln!(f, "Reserved:        {}", stats.reserved)?;
+        writeln!(f, "Farmers:         {}", stats.farmers)?;
+        writeln!(f, "Active stakes:   {}", stats.active_stakes)?;
+        writeln!(f, "Emission rate:   {} tokens/s", stats.emission_rate)?;
+
+        match stats.runway_days() {
+            Some(days) => writeln!(f, "Runway:          {:.1} days", days)?,
+            None => writeln!(f, "Runway:          no rewards are being emitted")?,
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:^44} | {:^22} | {:^22}",
+            "Whitelisted address", "Type", "Staked"
+        )?;
+        for (address, ty, staked) in &stats.staked_per_whitelist {
+            writeln!(
+                f,
+                "{:^44} | {:^22} | {:^22}",
+                address,
+                whitelist_type_name(*ty),
+                staked
+            )?;
+        }
+
+        if stats.unattributed_staked > 0 {
+            writeln!(
+                f,
+                "{:^44} | {:^22} | {:^22}",
+                "Unattributed", "-", stats.unattributed_staked
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Serialize for EventListOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.0)
+    }
+}
+
+impl Output for EventListOutput {}
+impl Display for EventListOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return writeln!(f, "No staking events found.");
+        }
+
+        for event in &self.0 {
+            writeln!(f, "{:?}", event)?;
+        }
+        Ok(())
+    }
+}
+
+fn stake_status(receipt: &StakeReceipt) -> String {
+    if receipt.is_running() {
+        "Staked".to_string()
+    } else if receipt.is_cooling_down() {
+        format!("Cooling down until {}", receipt.cooldown_end_ts)
+    } else {
+        "Unstaked".to_string()
+    }
+}
+
+impl Serialize for FarmerShowOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct StakeRow<'a> {
+            #[serde(serialize_with = "as_string")]
+            address: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            mint: &'a Pubkey,
+            amount: u64,
+            reward_rate: u64,
+            secondary_reward_rate: u64,
+            start_ts: u64,
+            end_ts: Option<u64>,
+            lock_end_ts: u64,
+            cooldown_end_ts: u64,
+            status: String,
+        }
+
+        #[derive(Serialize)]
+        struct Row<'a> {
+            #[serde(serialize_with = "as_string")]
+            farmer: &'a Pubkey,
+            #[serde(serialize_with = "as_string")]
+            owner: &'a Pubkey,
+            pending_rewards: Option<u64>,
+            pending_secondary_rewards: Option<u64>,
+            reward_shortfall: u64,
+            stakes: Vec<StakeRow<'a>>,
+        }
+
+        Row {
+            farmer: &self.farmer,
+            owner: &self.owner,
+            pending_rewards: self.pending_rewards,
+            pending_secondary_rewards: self.pending_secondary_rewards,
+            reward_shortfall: self.reward_shortfall,
+            stakes: self
+                .receipts
+                .iter()
+                .map(|(k, receipt)| StakeRow {
+                    address: k,
+                    mint: &receipt.mint,
+                    amount: receipt.amount,
+                    reward_rate: receipt.reward_rate,
+                    secondary_reward_rate: receipt.secondary_reward_rate,
+                    start_ts: receipt.start_ts,
+                    end_ts: receipt.end_ts,
+                    lock_end_ts: receipt.lock_end_ts,
+                    cooldown_end_ts: receipt.cooldown_end_ts,
+                    status: stake_status(receipt),
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl Output for FarmerShowOutput {}
+impl Display for FarmerShowOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Farmer:            {}", self.farmer)?;
+        writeln!(f, "Owner:             {}", self.owner)?;
+
+        match self.pending_rewards {
+            Some(pending) => writeln!(f, "Pending rewards:   {}", pending)?,
+            None => writeln!(f, "Pending rewards:   farm is out of rewards")?,
+        }
+
+        if let Some(pending) = self.pending_secondary_rewards {
+            writeln!(f, "Pending secondary: {}", pending)?;
+        }
+
+        writeln!(f, "Missed rewards:    {}", self.reward_shortfall)?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:^44} | {:^22} | {:^22} | {:^22} | {:^32}",
+            "Mint", "Amount", "Reward rate", "Unlocks at", "Status"
+        )?;
+        for (_, receipt) in &self.receipts {
+            writeln!(
+                f,
+                "{:^44} | {:^22} | {:^22} | {:^22} | {:^32}",
+                receipt.mint,
+                receipt.amount,
+                receipt.reward_rate,
+                receipt.lock_end_ts,
+                stake_status(receipt)
             )?;
         }
         Ok(())
@@ -0,0 +1,643 @@
+use std::collections::HashMap;
+
+use anchor_client::{
+    anchor_lang::{solana_program, system_program, Discriminator},
+    solana_client::rpc_config::RpcProgramAccountsConfig,
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+};
+use anchor_spl::associated_token::get_associated_token_address;
+use anyhow::{bail, Result};
+use dskullys_staking::{
+    accounts,
+    state::{FarmManager, WhitelistProof, WhitelistType},
+};
+
+use super::{
+    pda,
+    utils::{instruction, memcmp},
+    StakingClient,
+};
+
+/// Bytes left for the gems of a batch once the signature, message header,
+/// blockhash and the instruction's fixed accounts are in the transaction.
+const BATCH_TX_BUDGET: usize = 1232 - 65 - 3 - 32 - 9 * 32 - 24;
+
+/// Compute units a transaction gets without requesting more, and roughly how
+/// many the batch instruction uses besides its gems, mostly to load its fixed
+/// accounts and update the farmer's rewards once.
+const TX_COMPUTE_UNITS: usize = 200_000;
+const BATCH_COMPUTE_UNITS: usize = 30_000;
+
+/// Estimated compute used per gem. Staking creates the gem's vault through the
+/// associated token program, creates its receipt, checks the whitelist and
+/// moves the gem. Unstaking moves the gem back and closes the vault.
+const STAKE_GEM_COMPUTE_UNITS: usize = 55_000;
+const UNSTAKE_GEM_COMPUTE_UNITS: usize = 25_000;
+
+/// Most gems a batch can hold before running out of compute, the transaction
+/// size may limit them further, see `stake_gem_size` and `unstake_gem_size`.
+const STAKE_BATCH_GEMS: usize =
+    (TX_COMPUTE_UNITS - BATCH_COMPUTE_UNITS) / STAKE_GEM_COMPUTE_UNITS;
+const UNSTAKE_BATCH_GEMS: usize =
+    (TX_COMPUTE_UNITS - BATCH_COMPUTE_UNITS) / UNSTAKE_GEM_COMPUTE_UNITS;
+
+/// Most accounts closed per transaction, the transaction size usually limits
+/// them first.
+const CLOSE_BATCH_ACCOUNTS: usize = 20;
+
+impl StakingClient {
+    /// Stakes one NFT of each mint, grouping them per whitelist and sending as
+    /// few transactions as the size and compute limits allow.
+    pub fn stake_many(
+        &self,
+        farm: Pubkey,
+        gem_mints: &[Pubkey],
+        merkle_proofs: &HashMap<Pubkey, Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+        let no_proof = vec![];
+
+        let mut whitelists: HashMap<Pubkey, Vec<Pubkey>> = HashMap::new();
+        for gem_mint in gem_mints {
+            let merkle_proof = merkle_proofs.get(gem_mint).unwrap_or(&no_proof);
+            let whitelist_proof =
+                self.find_gem_whitelist(farm, gem_mint, merkle_proof)?;
+
+            whitelists.entry(whitelist_proof).or_default().push(*gem_mint);
+        }
+
+        for (whitelist_proof, gem_mints) in whitelists {
+            let whitelist: WhitelistProof =
+                self.program.account(whitelist_proof)?;
+            let with_metadata = match whitelist.ty {
+                WhitelistType::Creator | WhitelistType::Collection => true,
+                WhitelistType::MerkleRoot => false,
+                WhitelistType::Mint => bail!(
+                    "{} is a fungible token, stake it with `farmer stake --amount`",
+                    whitelist.whitelisted_address
+                ),
+            };
+
+            let batches = split_batches(gem_mints, STAKE_BATCH_GEMS, |mint| {
+                let proof_len = merkle_proofs.get(mint).map_or(0, Vec::len);
+                stake_gem_size(with_metadata, proof_len)
+            });
+
+            for batch in batches {
+                let accs = accounts::StakeMany {
+                    farm,
+                    farmer,
+                    secondary_reward: pda::find_secondary_reward_address(&farm),
+                    whitelist_proof,
+                    owner,
+                    rent: solana_program::sysvar::rent::ID,
+                    system_program: system_program::ID,
+                    token_program: anchor_spl::token::ID,
+                    associated_token_program: anchor_spl::associated_token::ID,
+                };
+
+                let mut gems = vec![];
+                let mut batch_proofs = vec![];
+                for gem_mint in &batch {
+                    gems.extend([
+                        AccountMeta::new_readonly(*gem_mint, false),
+                        AccountMeta::new(
+                            get_associated_token_address(&owner, gem_mint),
+                            false,
+                        ),
+                        AccountMeta::new(
+                            get_associated_token_address(&farmer, gem_mint),
+                            false,
+                        ),
+                        AccountMeta::new(
+                            pda::find_stake_receipt_address(farmer, *gem_mint),
+                            false,
+                        ),
+                        AccountMeta::new_readonly(
+                            pda::find_buff_proof_address(farm, *gem_mint),
+                            false,
+                        ),
+                    ]);
+
+                    if with_metadata {
+                        gems.push(AccountMeta::new_readonly(
+                            pda::find_metadata_address(gem_mint),
+                            false,
+                        ));
+                    }
+
+                    if whitelist.ty == WhitelistType::MerkleRoot {
+                        batch_proofs.push(
+                            merkle_proofs.get(gem_mint).unwrap_or(&no_proof).clone(),
+                        );
+                    }
+                }
+
+                let signature = self
+                    .program
+                    .request()
+                    .accounts(accs)
+                    .accounts(gems)
+                    .args(dskullys_staking::instruction::StakeMany {
+                        merkle_proofs: batch_proofs,
+                    })
+                    .signer(&*self.payer)
+                    .send()?;
+
+                eprintln!("Staked {} gems. Signature: {}", batch.len(), signature);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unstakes the given gems, or every gem that can be unstaked right now
+    /// when `gem_mints` is empty. Gems with a cooldown must have begun
+    /// unstaking first.
+    pub fn unstake_many(&self, farm: Pubkey, gem_mints: &[Pubkey]) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+
+        let gem_mints = if gem_mints.is_empty() {
+            let now = self.now_ts()?;
+
+            self.get_farmer_stake_receipts(farmer)?
+                .into_iter()
+                .filter(|(_, receipt)| {
+                    if receipt.is_running() {
+                        receipt.unstake_cooldown == 0 && receipt.lock_end_ts <= now
+                    } else {
+                        receipt.is_cooling_down() && receipt.cooldown_end_ts <= now
+                    }
+                })
+                .map(|(_, receipt)| receipt.mint)
+                .collect()
+        } else {
+            gem_mints.to_vec()
+        };
+
+        if gem_mints.is_empty() {
+            eprintln!("No gems can be unstaked right now.");
+            return Ok(());
+        }
+
+        let batches =
+            split_batches(gem_mints, UNSTAKE_BATCH_GEMS, |_| unstake_gem_size());
+
+        for batch in batches {
+            let accs = accounts::UnstakeMany {
+                farm,
+                farmer,
+                secondary_reward: pda::find_secondary_reward_address(&farm),
+                owner,
+                rent: solana_program::sysvar::rent::ID,
+                system_program: system_program::ID,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+            };
+
+            let gems: Vec<AccountMeta> = batch
+                .iter()
+                .flat_map(|gem_mint| {
+                    [
+                        AccountMeta::new_readonly(*gem_mint, false),
+                        AccountMeta::new(
+                            pda::find_stake_receipt_address(farmer, *gem_mint),
+                            false,
+                        ),
+                        AccountMeta::new(
+                            get_associated_token_address(&farmer, gem_mint),
+                            false,
+                        ),
+                        AccountMeta::new(
+                            get_associated_token_address(&owner, gem_mint),
+                            false,
+                        ),
+                    ]
+                })
+                .collect();
+
+            let signature = self
+                .program
+                .request()
+                .accounts(accs)
+                .accounts(gems)
+                .args(dskullys_staking::instruction::UnstakeMany)
+                .signer(&*self.payer)
+                .send()?;
+
+            eprintln!("Unstaked {} gems. Signature: {}", batch.len(), signature);
+        }
+
+        Ok(())
+    }
+
+    /// Closes the farmer's stake receipts whose gems were unstaked, and the
+    /// farmer itself with `close_farmer` once nothing is staked nor owed.
+    pub fn reclaim_rent(&self, farm: Pubkey, close_farmer: bool) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+
+        let receipts = self.get_farmer_stake_receipts(farmer)?;
+        let (open, closable): (Vec<_>, Vec<_>) =
+            receipts.into_iter().partition(|(_, receipt)| {
+                receipt.is_running() || receipt.is_cooling_down()
+            });
+
+        for batch in closable.chunks(CLOSE_BATCH_ACCOUNTS) {
+            let mut request = self.program.request();
+            for (stake_receipt, _) in batch {
+                let accs = accounts::CloseStakeReceipt {
+                    farmer,
+                    stake_receipt: *stake_receipt,
+                    owner,
+                };
+
+                request = request.instruction(instruction(
+                    accs,
+                    dskullys_staking::instruction::CloseStakeReceipt,
+                ));
+            }
+
+            let signature = request.signer(&*self.payer).send()?;
+
+            eprintln!(
+                "Closed {} stake receipts. Signature: {}",
+                batch.len(),
+                signature
+            );
+        }
+
+        if !close_farmer {
+            return Ok(());
+        }
+
+        if !open.is_empty() {
+            bail!(
+                "{} gems are still staked, unstake them before closing the farmer",
+                open.len()
+            );
+        }
+
+        let (_, farmer_account) = self.get_farmer(farm, owner)?;
+        if !farmer_account.is_empty() {
+            bail!("Claim the farmer's rewards before closing it");
+        }
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accounts::CloseFarmer {
+                farm,
+                farmer,
+                owner,
+            })
+            .args(dskullys_staking::instruction::CloseFarmer)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!("Closed farmer {}. Signature: {}", farmer, signature);
+
+        Ok(())
+    }
+
+    /// Closes every whitelist, buff and manager of the farm, its pending
+    /// authority transfer and secondary reward, and then the farm itself,
+    /// sending the rewards left to the authority.
+    pub fn close_farm(&self, farm: Pubkey) -> Result<()> {
+        let authority = self.payer.pubkey();
+        let farm_account = self.get_farm(farm)?;
+
+        if farm_account.authority != authority {
+            bail!("Only the farm authority can close it");
+        }
+
+        if !farm_account.stakes_counted {
+            bail!(
+                "Farm {} has stakes made before they were counted, upgrade it \
+                 first",
+                farm
+            );
+        }
+        if farm_account.active_stakes > 0 {
+            bail!(
+                "{} gems are still staked in farm {}",
+                farm_account.active_stakes,
+                farm
+            );
+        }
+        if farm_account.reward.reserved > 0 {
+            bail!(
+                "Farmers are still owed {} reward tokens",
+                farm_account.reward.reserved
+            );
+        }
+
+        let secondary_reward = self.get_secondary_reward(farm)?;
+        if let Some(secondary_reward) = &secondary_reward {
+            if secondary_reward.reward.reserved > 0 {
+                bail!(
+                    "Farmers are still owed {} secondary reward tokens",
+                    secondary_reward.reward.reserved
+                );
+            }
+        }
+
+        // Whitelists and buffs can only be removed through a manager.
+        let farm_manager = pda::find_farm_manager_address(farm, authority);
+        let manager_owners = self.find_farm_manager_owners(farm)?;
+        let permissions = FarmManager::WHITELIST | FarmManager::RATES;
+        let can_remove = self
+            .program
+            .account::<FarmManager>(farm_manager)
+            .map_or(false, |manager| manager.can(permissions));
+        if !can_remove {
+            if manager_owners.contains(&authority) {
+                self.remove_manager(farm, authority)?;
+            }
+            self.add_manager(farm, None, FarmManager::ALL)?;
+        }
+
+        let whitelists = self.get_farm_whitelists(farm)?.into_iter().map(
+            |(whitelist_proof, _)| {
+                instruction(
+                    accounts::RemoveFromWhitelist {
+                        farm,
+                        farm_manager,
+                        whitelist_proof,
+                        authority,
+                        system_program: system_program::ID,
+                    },
+                    dskullys_staking::instruction::RemoveFromWhitelist,
+                )
+            },
+        );
+        self.send_closing_batches(whitelists.collect(), 1, "whitelists")?;
+
+        let buffs =
+            self.get_farm_buffs(farm)?.into_iter().map(|(buff_proof, _)| {
+                instruction(
+                    accounts::RemoveBuff {
+                        farm,
+                        farm_manager,
+                        buff_proof,
+                        authority,
+                        system_program: system_program::ID,
+                    },
+                    dskullys_staking::instruction::RemoveBuff,
+                )
+            });
+        self.send_closing_batches(buffs.collect(), 1, "buffs")?;
+
+        // The authority's own manager goes last, it was needed until now.
+        let mut managers: Vec<Pubkey> = manager_owners
+            .into_iter()
+            .filter(|owner| *owner != authority)
+            .collect();
+        managers.push(authority);
+        let managers = managers.into_iter().map(|manager_authority| {
+            instruction(
+                accounts::RemoveManager {
+                    farm,
+                    farm_manager: pda::find_farm_manager_address(
+                        farm,
+                        manager_authority,
+                    ),
+                    manager_authority,
+                    authority,
+                    system_program: system_program::ID,
+                },
+                dskullys_staking::instruction::RemoveManager,
+            )
+        });
+        // Each one adds its manager account and the manager's authority.
+        self.send_closing_batches(managers.collect(), 2, "managers")?;
+
+        let authority_transfer = pda::find_authority_transfer_address(&farm);
+        if self.program.rpc().get_account(&authority_transfer).is_ok() {
+            self.cancel_authority_transfer(farm)?;
+        }
+
+        if secondary_reward.is_some() {
+            self.close_secondary_reward(farm)?;
+        }
+
+        let reward_mint = farm_account.reward.mint;
+        let accs = accounts::CloseFarm {
+            farm,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            reward_mint,
+            farm_reward_vault: get_associated_token_address(
+                &farm,
+                &reward_mint,
+            ),
+            authority_ata: get_associated_token_address(
+                &authority,
+                &reward_mint,
+            ),
+            authority,
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::CloseFarm)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!("Closed farm {}. Signature: {}", farm, signature);
+
+        Ok(())
+    }
+
+    /// Owners of the farm's managers, read from the raw accounts so the ones
+    /// created before permissions were added are found too.
+    fn find_farm_manager_owners(&self, farm: Pubkey) -> Result<Vec<Pubkey>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                memcmp(0, &FarmManager::discriminator()),
+                memcmp(8, farm.as_ref()),
+            ]),
+            ..Default::default()
+        };
+
+        let accounts = self
+            .program
+            .rpc()
+            .get_program_accounts_with_config(&dskullys_staking::ID, config)?;
+
+        Ok(accounts
+            .into_iter()
+            .map(|(_, account)| Pubkey::new(&account.data[40..72]))
+            .collect())
+    }
+
+    /// Sends the closing `instructions` in as few transactions as fit, each
+    /// instruction adding `new_accounts` addresses besides the farm, its
+    /// authority and the programs they all share.
+    fn send_closing_batches(
+        &self,
+        instructions: Vec<Instruction>,
+        new_accounts: usize,
+        accounts_name: &str,
+    ) -> Result<()> {
+        // Program index, account indexes, data and their lengths.
+        let batches = split_batches(instructions, CLOSE_BATCH_ACCOUNTS, |ix| {
+            new_accounts * 32 + ix.accounts.len() + ix.data.len() + 3
+        });
+
+        for batch in batches {
+            let mut request = self.program.request();
+            for ix in &batch {
+                request = request.instruction(ix.clone());
+            }
+
+            let signature = request.signer(&*self.payer).send()?;
+
+            eprintln!(
+                "Closed {} {}. Signature: {}",
+                batch.len(),
+                accounts_name,
+                signature
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Bytes a gem adds to a `stake_many` transaction: an address and an account
+/// index for each of its accounts, and its merkle proof with its length.
+fn stake_gem_size(with_metadata: bool, proof_len: usize) -> usize {
+    let gem_accounts = dskullys_staking::instructions::STAKE_MANY_GEM_ACCOUNTS
+        + with_metadata as usize;
+
+    gem_accounts * 33 + 4 + proof_len * 32
+}
+
+/// Bytes a gem adds to an `unstake_many` transaction, an address and an
+/// account index for each of its accounts.
+fn unstake_gem_size() -> usize {
+    dskullys_staking::instructions::UNSTAKE_MANY_GEM_ACCOUNTS * 33
+}
+
+/// Splits `items` into batches of at most `max_items`, whose sizes add up to
+/// no more than `BATCH_TX_BUDGET` bytes.
+fn split_batches<T>(
+    items: Vec<T>,
+    max_items: usize,
+    size_of: impl Fn(&T) -> usize,
+) -> Vec<Vec<T>> {
+    let mut batches = vec![];
+    let mut batch = vec![];
+    let mut batch_size = 0;
+
+    for item in items {
+        let size = size_of(&item);
+
+        if !batch.is_empty()
+            && (batch.len() == max_items || batch_size + size > BATCH_TX_BUDGET)
+        {
+            batches.push(std::mem::take(&mut batch));
+            batch_size = 0;
+        }
+
+        batch_size += size;
+        batch.push(item);
+    }
+
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batches_stop_at_the_item_limit() {
+        let batches = split_batches((0..7).collect(), 3, |_| 1);
+
+        assert_eq!(batches, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+    }
+
+    #[test]
+    fn batches_stay_within_the_transaction_budget() {
+        let size = BATCH_TX_BUDGET / 2;
+        let batches = split_batches(vec![size, size, 1, size], 10, |s| *s);
+
+        assert_eq!(batches, vec![vec![size, size], vec![1, size]]);
+    }
+
+    #[test]
+    fn oversized_items_get_their_own_batch() {
+        let size = BATCH_TX_BUDGET + 1;
+        let batches = split_batches(vec![1, size, 1], 10, |s| *s);
+
+        assert_eq!(batches, vec![vec![1], vec![size], vec![1]]);
+        assert!(split_batches(Vec::<usize>::new(), 10, |s| *s).is_empty());
+    }
+
+    fn gem_batch_lens(
+        gems: usize,
+        max_gems: usize,
+        gem_size: usize,
+    ) -> Vec<usize> {
+        split_batches(vec![gem_size; gems], max_gems, |size| *size)
+            .iter()
+            .map(Vec::len)
+            .collect()
+    }
+
+    #[test]
+    fn stake_batches_fit_the_compute_limit() {
+        assert_eq!(STAKE_BATCH_GEMS, 3);
+
+        for with_metadata in [false, true] {
+            let size = stake_gem_size(with_metadata, 0);
+            assert!(STAKE_BATCH_GEMS * size <= BATCH_TX_BUDGET);
+
+            assert_eq!(gem_batch_lens(3, STAKE_BATCH_GEMS, size), vec![3]);
+            assert_eq!(gem_batch_lens(4, STAKE_BATCH_GEMS, size), vec![3, 1]);
+        }
+    }
+
+    #[test]
+    fn stake_batches_with_long_proofs_fit_the_transaction() {
+        // Gems of a merkle whitelist of 32 mints fit 2 per transaction, those
+        // of one with 256 mints or more only 1.
+        let size = stake_gem_size(false, 8);
+        assert_eq!(BATCH_TX_BUDGET / size, 1);
+        let size = stake_gem_size(false, 5);
+        assert_eq!(BATCH_TX_BUDGET / size, 2);
+
+        assert_eq!(gem_batch_lens(2, STAKE_BATCH_GEMS, size), vec![2]);
+        assert_eq!(gem_batch_lens(3, STAKE_BATCH_GEMS, size), vec![2, 1]);
+
+        let size = stake_gem_size(false, 14);
+        assert_eq!(gem_batch_lens(2, STAKE_BATCH_GEMS, size), vec![1, 1]);
+    }
+
+    #[test]
+    fn unstake_batches_fit_the_compute_and_size_limits() {
+        assert_eq!(UNSTAKE_BATCH_GEMS, 6);
+
+        let size = unstake_gem_size();
+        assert!(UNSTAKE_BATCH_GEMS * size <= BATCH_TX_BUDGET);
+
+        assert_eq!(gem_batch_lens(6, UNSTAKE_BATCH_GEMS, size), vec![6]);
+        assert_eq!(gem_batch_lens(7, UNSTAKE_BATCH_GEMS, size), vec![6, 1]);
+    }
+}
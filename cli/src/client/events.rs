@@ -0,0 +1,431 @@
+use anchor_client::anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::Result;
+use dskullys_staking::events::*;
+use serde::{Serialize, Serializer};
+use serde_json::json;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Events emitted by the staking program.
+#[derive(Debug)]
+pub enum StakingEvent {
+    FarmCreated(FarmCreated),
+    ManagerAdded(ManagerAdded),
+    Whitelisted(Whitelisted),
+    Staked(Staked),
+    Unstaked(Unstaked),
+    RewardsClaimed(RewardsClaimed),
+    RewardFunded(RewardFunded),
+    ForceUnstaked(ForceUnstaked),
+    FarmStateChanged(FarmStateChanged),
+    FarmClosed(FarmClosed),
+    AuthorityTransferCancelled(AuthorityTransferCancelled),
+    RewardWithdrawn(RewardWithdrawn),
+    ManagerRemoved(ManagerRemoved),
+    UnstakeBegun(UnstakeBegun),
+    StakeRefreshed(StakeRefreshed),
+    AccrualPolicyChanged(AccrualPolicyChanged),
+    RewardWindowChanged(RewardWindowChanged),
+    AuthorityProposed(AuthorityProposed),
+    AuthorityAccepted(AuthorityAccepted),
+    StakeMigrationFinished(StakeMigrationFinished),
+    WhitelistUpdated(WhitelistUpdated),
+    WhitelistRemoved(WhitelistRemoved),
+    BuffAdded(BuffAdded),
+    BuffRemoved(BuffRemoved),
+    SecondaryRewardAdded(SecondaryRewardAdded),
+    SecondaryRewardClosed(SecondaryRewardClosed),
+    FarmerInitialized(FarmerInitialized),
+    FarmerClosed(FarmerClosed),
+    StakeReceiptClosed(StakeReceiptClosed),
+}
+
+impl StakingEvent {
+    /// Decodes an event from its discriminator followed by its borsh serialized data.
+    pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+        if data.len() < 8 {
+            return Ok(None);
+        }
+
+        let (discriminator, mut data) = data.split_at(8);
+
+        macro_rules! decode {
+            ($($event:ident),*) => {
+                $(
+                    if discriminator == $event::discriminator() {
+                        let event = $event::deserialize(&mut data)?;
+                        return Ok(Some(Self::$event(event)));
+                    }
+                )*
+            };
+        }
+
+        decode!(
+            FarmCreated,
+            ManagerAdded,
+            Whitelisted,
+            Staked,
+            Unstaked,
+            RewardsClaimed,
+            RewardFunded,
+            ForceUnstaked,
+            FarmStateChanged,
+            FarmClosed,
+            AuthorityTransferCancelled,
+            RewardWithdrawn,
+            ManagerRemoved,
+            UnstakeBegun,
+            StakeRefreshed,
+            AccrualPolicyChanged,
+            RewardWindowChanged,
+            AuthorityProposed,
+            AuthorityAccepted,
+            StakeMigrationFinished,
+            WhitelistUpdated,
+            WhitelistRemoved,
+            BuffAdded,
+            BuffRemoved,
+            SecondaryRewardAdded,
+            SecondaryRewardClosed,
+            FarmerInitialized,
+            FarmerClosed,
+            StakeReceiptClosed
+        );
+
+        Ok(None)
+    }
+}
+
+impl Serialize for StakingEvent {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let (event, data) = match self {
+            Self::FarmCreated(e) => (
+                "FarmCreated",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "authority": e.authority.to_string(),
+                    "reward_mint": e.reward_mint.to_string(),
+                }),
+            ),
+            Self::ManagerAdded(e) => (
+                "ManagerAdded",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "manager": e.manager.to_string(),
+                    "permissions": e.permissions,
+                }),
+            ),
+            Self::Whitelisted(e) => (
+                "Whitelisted",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "whitelisted_address": e.whitelisted_address.to_string(),
+                    "type": format!("{:?}", e.ty),
+                    "reward_rate": e.reward_rate,
+                }),
+            ),
+            Self::Staked(e) => (
+                "Staked",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                    "mint": e.mint.to_string(),
+                    "amount": e.amount,
+                    "reward_rate": e.reward_rate,
+                }),
+            ),
+            Self::Unstaked(e) => (
+                "Unstaked",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                    "mint": e.mint.to_string(),
+                    "amount": e.amount,
+                }),
+            ),
+            Self::RewardsClaimed(e) => (
+                "RewardsClaimed",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                    "mint": e.mint.to_string(),
+                    "amount": e.amount,
+                }),
+            ),
+            Self::RewardFunded(e) => (
+                "RewardFunded",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "funder": e.funder.to_string(),
+                    "mint": e.mint.to_string(),
+                    "amount": e.amount,
+                }),
+            ),
+            Self::ForceUnstaked(e) => (
+                "ForceUnstaked",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                    "mint": e.mint.to_string(),
+                    "amount": e.amount,
+                    "authority": e.authority.to_string(),
+                }),
+            ),
+            Self::FarmStateChanged(e) => (
+                "FarmStateChanged",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "state": format!("{:?}", e.state),
+                }),
+            ),
+            Self::FarmClosed(e) => (
+                "FarmClosed",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "authority": e.authority.to_string(),
+                    "amount": e.amount,
+                }),
+            ),
+            Self::AuthorityTransferCancelled(e) => (
+                "AuthorityTransferCancelled",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "authority": e.authority.to_string(),
+                    "new_authority": e.new_authority.to_string(),
+                }),
+            ),
+            Self::RewardWithdrawn(e) => (
+                "RewardWithdrawn",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "authority": e.authority.to_string(),
+                    "mint": e.mint.to_string(),
+                    "amount": e.amount,
+                }),
+            ),
+            Self::ManagerRemoved(e) => (
+                "ManagerRemoved",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "manager": e.manager.to_string(),
+                }),
+            ),
+            Self::UnstakeBegun(e) => (
+                "UnstakeBegun",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                    "mint": e.mint.to_string(),
+                    "amount": e.amount,
+                    "cooldown_end_ts": e.cooldown_end_ts,
+                }),
+            ),
+            Self::StakeRefreshed(e) => (
+                "StakeRefreshed",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                    "mint": e.mint.to_string(),
+                    "reward_rate": e.reward_rate,
+                    "secondary_reward_rate": e.secondary_reward_rate,
+                }),
+            ),
+            Self::AccrualPolicyChanged(e) => (
+                "AccrualPolicyChanged",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "policy": format!("{:?}", e.policy),
+                }),
+            ),
+            Self::RewardWindowChanged(e) => (
+                "RewardWindowChanged",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "start_ts": e.start_ts,
+                    "end_ts": e.end_ts,
+                }),
+            ),
+            Self::AuthorityProposed(e) => (
+                "AuthorityProposed",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "authority": e.authority.to_string(),
+                    "new_authority": e.new_authority.to_string(),
+                }),
+            ),
+            Self::AuthorityAccepted(e) => (
+                "AuthorityAccepted",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "previous_authority": e.previous_authority.to_string(),
+                    "new_authority": e.new_authority.to_string(),
+                }),
+            ),
+            Self::StakeMigrationFinished(e) => (
+                "StakeMigrationFinished",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "active_stakes": e.active_stakes,
+                }),
+            ),
+            Self::WhitelistUpdated(e) => (
+                "WhitelistUpdated",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "whitelisted_address": e.whitelisted_address.to_string(),
+                    "reward_rate": e.reward_rate,
+                    "secondary_reward_rate": e.secondary_reward_rate,
+                    "rate_epoch": e.rate_epoch,
+                }),
+            ),
+            Self::WhitelistRemoved(e) => (
+                "WhitelistRemoved",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "whitelisted_address": e.whitelisted_address.to_string(),
+                }),
+            ),
+            Self::BuffAdded(e) => (
+                "BuffAdded",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "mint": e.mint.to_string(),
+                    "factor": e.factor,
+                }),
+            ),
+            Self::BuffRemoved(e) => (
+                "BuffRemoved",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "mint": e.mint.to_string(),
+                }),
+            ),
+            Self::SecondaryRewardAdded(e) => (
+                "SecondaryRewardAdded",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "mint": e.mint.to_string(),
+                }),
+            ),
+            Self::SecondaryRewardClosed(e) => (
+                "SecondaryRewardClosed",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "authority": e.authority.to_string(),
+                    "amount": e.amount,
+                }),
+            ),
+            Self::FarmerInitialized(e) => (
+                "FarmerInitialized",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                }),
+            ),
+            Self::FarmerClosed(e) => (
+                "FarmerClosed",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                }),
+            ),
+            Self::StakeReceiptClosed(e) => (
+                "StakeReceiptClosed",
+                json!({
+                    "farm": e.farm.to_string(),
+                    "owner": e.owner.to_string(),
+                    "mint": e.mint.to_string(),
+                }),
+            ),
+        };
+
+        json!({ "event": event, "data": data }).serialize(serializer)
+    }
+}
+
+/// Parses the staking events out of a transaction's log messages.
+///
+/// Only data logged while the staking program is the innermost running program is decoded, so
+/// events from other programs sharing the transaction are ignored.
+pub fn parse_logs(logs: &[String]) -> Result<Vec<StakingEvent>> {
+    let program_id = dskullys_staking::ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if stack.last() == Some(&program_id.as_str()) {
+                if let Some(event) = StakingEvent::decode(&base64::decode(data)?)? {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let (program, status) = (words.next(), words.next());
+
+            match (program, status) {
+                (Some(program), Some("invoke")) => stack.push(program),
+                (Some(_), Some("success" | "failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_client::{anchor_lang::Event, solana_sdk::pubkey::Pubkey};
+
+    fn logs(program: &str, data: &[u8]) -> Vec<String> {
+        vec![
+            format!("Program {} invoke [1]", program),
+            format!("{}{}", PROGRAM_DATA, base64::encode(data)),
+            format!("Program {} success", program),
+        ]
+    }
+
+    #[test]
+    fn events_round_trip_through_the_logs() {
+        let event = BuffAdded {
+            farm: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            factor: 150,
+        };
+
+        let events =
+            parse_logs(&logs(&dskullys_staking::ID.to_string(), &event.data()))
+                .unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            serde_json::to_value(&events[0]).unwrap(),
+            json!({
+                "event": "BuffAdded",
+                "data": {
+                    "farm": event.farm.to_string(),
+                    "mint": event.mint.to_string(),
+                    "factor": 150,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn events_of_other_programs_are_ignored() {
+        let event = FarmerClosed {
+            farm: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+        };
+
+        let other = Pubkey::new_unique().to_string();
+        assert!(parse_logs(&logs(&other, &event.data())).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,616 @@
+use anchor_client::{
+    anchor_lang::{solana_program, system_program},
+    solana_sdk::{
+        account::from_account,
+        clock::Clock,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        sysvar,
+    },
+};
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use dskullys_staking::{
+    accounts,
+    state::{Farm, Farmer, StakeReceipt, WhitelistProof, WhitelistType},
+};
+
+use super::{merkle, pda, utils::*, StakingClient};
+
+/// Stakes refreshed by a claim, more wouldn't fit in the transaction. The rest
+/// are refreshed by the following claims.
+const CLAIM_REFRESH_STAKES: usize = 4;
+
+impl StakingClient {
+    pub fn get_farmer(
+        &self,
+        farm: Pubkey,
+        owner: Pubkey,
+    ) -> Result<(Pubkey, Farmer)> {
+        let farmer = pda::find_farmer_address(farm, owner);
+        let account = self
+            .program
+            .account(farmer)
+            .with_context(|| format!("{} is not a farmer of {}", owner, farm))?;
+
+        Ok((farmer, account))
+    }
+
+    /// Rewards the farmer would receive by claiming now, computed the same way
+    /// the program does.
+    pub fn pending_rewards(&self, farmer: Pubkey) -> Result<u64> {
+        let farmer_account: Farmer = self
+            .program
+            .account(farmer)
+            .with_context(|| format!("Farmer {} not found", farmer))?;
+        let farm = self.get_farm(farmer_account.farm)?;
+
+        farmer_account
+            .pending_rewards(&farm, self.now_ts()?)
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// Secondary rewards that claiming now would pay out, computed the same way
+    /// the program does. Fails for farms without a secondary reward.
+    pub fn pending_secondary_rewards(&self, farmer: Pubkey) -> Result<u64> {
+        let farmer_account: Farmer = self
+            .program
+            .account(farmer)
+            .with_context(|| format!("Farmer {} not found", farmer))?;
+        let farm = self.get_farm(farmer_account.farm)?;
+        let secondary_reward = self
+            .get_secondary_reward(farmer_account.farm)?
+            .with_context(|| {
+                format!("Farm {} has no secondary reward", farmer_account.farm)
+            })?;
+
+        farmer_account
+            .pending_secondary_rewards(
+                &farm,
+                &secondary_reward.reward,
+                self.now_ts()?,
+            )
+            .map_err(|e| anyhow!("{}", e))
+    }
+
+    /// The cluster's current unix timestamp, as seen by the program.
+    pub(super) fn now_ts(&self) -> Result<u64> {
+        let clock_account = self.program.rpc().get_account(&sysvar::clock::ID)?;
+        let clock: Clock = from_account(&clock_account)
+            .ok_or_else(|| anyhow!("Failed to read the clock sysvar"))?;
+
+        Ok(clock.unix_timestamp as u64)
+    }
+
+    pub fn get_farmer_stake_receipts(
+        &self,
+        farmer: Pubkey,
+    ) -> Result<Vec<(Pubkey, StakeReceipt)>> {
+        find_accounts(&self.program, &[memcmp(8, farmer.as_ref())])
+    }
+
+    /// Running stakes of the farmer whose whitelist changed its rates since,
+    /// with the whitelist proof they were staked through.
+    fn get_outdated_stakes(
+        &self,
+        farmer: Pubkey,
+    ) -> Result<Vec<(Pubkey, StakeReceipt)>> {
+        let mut whitelists: HashMap<Pubkey, Option<WhitelistProof>> =
+            HashMap::new();
+        let mut outdated = vec![];
+
+        for (address, receipt) in self.get_farmer_stake_receipts(farmer)? {
+            if !receipt.is_running() || !receipt.is_migrated() {
+                continue;
+            }
+
+            let whitelist =
+                whitelists.entry(receipt.whitelist).or_insert_with(|| {
+                    self.program.account(receipt.whitelist).ok()
+                });
+
+            if let Some(wl) = whitelist {
+                if receipt.rate_epoch < wl.rate_epoch {
+                    outdated.push((address, receipt));
+                }
+            }
+        }
+
+        Ok(outdated)
+    }
+
+    pub fn initialize_farmer(&self, farm: Pubkey) -> Result<()> {
+        let farmer = pda::find_farmer_address(farm, self.payer.pubkey());
+
+        let accs = accounts::InitializeFarmer {
+            farm,
+            farmer,
+            owner: self.payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::InitializeFarmer)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!("Farmer created at {}. Signature: {}", farmer, signature);
+
+        Ok(())
+    }
+
+    /// Stakes `amount` tokens of `gem_mint`. `merkle_proof` is only needed when
+    /// the gem is whitelisted through a merkle root.
+    pub fn stake(
+        &self,
+        farm: Pubkey,
+        gem_mint: Pubkey,
+        amount: u64,
+        merkle_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+        let whitelist_proof =
+            self.find_gem_whitelist(farm, &gem_mint, &merkle_proof)?;
+
+        let accs = accounts::Stake {
+            farm,
+            farmer,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            gem_mint,
+            whitelist_proof,
+            buff_proof: pda::find_buff_proof_address(farm, gem_mint),
+            farmer_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farmer, &gem_mint,
+                ),
+            gem_owner_ata:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &owner, &gem_mint,
+                ),
+            stake_receipt: pda::find_stake_receipt_address(farmer, gem_mint),
+            owner,
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .accounts(AccountMeta::new_readonly(
+                pda::find_metadata_address(&gem_mint),
+                false,
+            ))
+            .args(dskullys_staking::instruction::Stake {
+                amount,
+                merkle_proof,
+            })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!("Staked {} {}. Signature: {}", amount, gem_mint, signature);
+
+        Ok(())
+    }
+
+    /// Unstakes a gem. Gems with an unstake cooldown are unstaked in two steps:
+    /// the first call begins the cooldown, and calling it again once the
+    /// cooldown is over withdraws them. The transaction that withdraws the
+    /// gems also pays the rewards with `claim`, and returns the receipt's rent
+    /// with `close`.
+    pub fn unstake(
+        &self,
+        farm: Pubkey,
+        gem_mint: Pubkey,
+        claim: bool,
+        close: bool,
+    ) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+        let stake_receipt = pda::find_stake_receipt_address(farmer, gem_mint);
+        let receipt: StakeReceipt = self
+            .program
+            .account(stake_receipt)
+            .with_context(|| format!("{} is not staked", gem_mint))?;
+
+        if self.program.rpc().get_account(&farm).is_err() {
+            return self.withdraw_orphaned_stake(farm, gem_mint);
+        }
+
+        if receipt.is_running() && receipt.unstake_cooldown > 0 {
+            let accs = accounts::BeginUnstake {
+                farm,
+                farmer,
+                secondary_reward: pda::find_secondary_reward_address(&farm),
+                gem_mint,
+                stake_receipt,
+                owner,
+            };
+
+            let signature = self
+                .program
+                .request()
+                .accounts(accs)
+                .args(dskullys_staking::instruction::BeginUnstake)
+                .signer(&*self.payer)
+                .send()?;
+
+            eprintln!(
+                "Began unstaking {}, run unstake again in {} seconds. Signature: {}",
+                gem_mint, receipt.unstake_cooldown, signature
+            );
+
+            return Ok(());
+        }
+
+        let instructions = if claim {
+            self.unstake_and_claim_instructions(farm, gem_mint)?
+        } else {
+            vec![self.unstake_instruction(farm, gem_mint)]
+        };
+
+        let mut request = self.program.request();
+        for instruction in instructions {
+            request = request.instruction(instruction);
+        }
+        if close {
+            request = request
+                .accounts(accounts::CloseStakeReceipt {
+                    farmer,
+                    stake_receipt,
+                    owner,
+                })
+                .args(dskullys_staking::instruction::CloseStakeReceipt);
+        }
+
+        let signature = request.signer(&*self.payer).send()?;
+
+        eprintln!("Unstaked {}. Signature: {}", gem_mint, signature);
+
+        Ok(())
+    }
+
+    /// Withdraws a gem from a farm that was closed while it was still staked.
+    /// Its rewards are lost with the farm, and the receipt is closed.
+    fn withdraw_orphaned_stake(
+        &self,
+        farm: Pubkey,
+        gem_mint: Pubkey,
+    ) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+
+        let accs = accounts::WithdrawOrphanedStake {
+            farm,
+            farmer,
+            gem_mint,
+            stake_receipt: pda::find_stake_receipt_address(farmer, gem_mint),
+            farmer_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farmer, &gem_mint,
+                ),
+            gem_owner_ata:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &owner, &gem_mint,
+                ),
+            owner,
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::WithdrawOrphanedStake)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Farm {} is closed, withdrew {}. Signature: {}",
+            farm, gem_mint, signature
+        );
+
+        Ok(())
+    }
+
+    fn unstake_instruction(&self, farm: Pubkey, gem_mint: Pubkey) -> Instruction {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+        let stake_receipt = pda::find_stake_receipt_address(farmer, gem_mint);
+
+        let accs = accounts::Unstake {
+            farm,
+            farmer,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            gem_mint,
+            stake_receipt,
+            farmer_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farmer, &gem_mint,
+                ),
+            gem_owner_ata:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &owner, &gem_mint,
+                ),
+            owner,
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        instruction(accs, dskullys_staking::instruction::Unstake)
+    }
+
+    /// The unstake and claim instruction, after the one creating the farmer's
+    /// secondary reward token account when it's missing.
+    fn unstake_and_claim_instructions(
+        &self,
+        farm: Pubkey,
+        gem_mint: Pubkey,
+    ) -> Result<Vec<Instruction>> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+        let reward_mint = self.get_farm(farm)?.reward.mint;
+
+        let accs = accounts::UnstakeAndClaim {
+            farm,
+            farmer,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            gem_mint,
+            stake_receipt: pda::find_stake_receipt_address(farmer, gem_mint),
+            farmer_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farmer, &gem_mint,
+                ),
+            gem_owner_ata:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &owner, &gem_mint,
+                ),
+            reward_mint,
+            farm_reward_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farm, &reward_mint,
+                ),
+            farmer_reward_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &owner, &reward_mint,
+                ),
+            owner,
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let mut unstake =
+            instruction(accs, dskullys_staking::instruction::UnstakeAndClaim);
+
+        let (secondary_accounts, create_ata) =
+            self.secondary_reward_accounts(farm)?;
+        unstake.accounts.extend(secondary_accounts);
+
+        Ok(create_ata.into_iter().chain([unstake]).collect())
+    }
+
+    /// Unstakes `amount` tokens of a fungible stake, leaving the rest staked.
+    pub fn partial_unstake(
+        &self,
+        farm: Pubkey,
+        gem_mint: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+        let stake_receipt = pda::find_stake_receipt_address(farmer, gem_mint);
+
+        let accs = accounts::PartialUnstake {
+            farm,
+            farmer,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            gem_mint,
+            stake_receipt: pda::find_stake_receipt_address(farmer, gem_mint),
+            farmer_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farmer, &gem_mint,
+                ),
+            gem_owner_ata:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &owner, &gem_mint,
+                ),
+            owner,
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let mut request = self.program.request().accounts(accs);
+
+        // What stays staked moves to the whitelist's current rates.
+        if let Some((_, receipt)) = self
+            .get_outdated_stakes(farmer)?
+            .into_iter()
+            .find(|(address, _)| *address == stake_receipt)
+        {
+            request = request.accounts(vec![
+                AccountMeta::new_readonly(receipt.whitelist, false),
+                AccountMeta::new_readonly(
+                    pda::find_buff_proof_address(farm, gem_mint),
+                    false,
+                ),
+            ]);
+        }
+
+        let signature = request
+            .args(dskullys_staking::instruction::PartialUnstake { amount })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Unstaked {} {} tokens. Signature: {}",
+            amount, gem_mint, signature
+        );
+
+        Ok(())
+    }
+
+    /// The secondary reward vaults claims take as remaining accounts, none for
+    /// farms without a secondary reward. The farmer's token account must
+    /// already exist, along with them comes the instruction creating it when
+    /// it doesn't.
+    fn secondary_reward_accounts(
+        &self,
+        farm: Pubkey,
+    ) -> Result<(Vec<AccountMeta>, Option<Instruction>)> {
+        let owner = self.payer.pubkey();
+        let secondary_reward = match self.get_secondary_reward(farm)? {
+            Some(secondary_reward) => secondary_reward,
+            None => return Ok((vec![], None)),
+        };
+
+        let mint = secondary_reward.reward.mint;
+        let farmer_ata =
+            anchor_spl::associated_token::get_associated_token_address(
+                &owner, &mint,
+            );
+
+        let missing = self.program.rpc().get_account(&farmer_ata).is_err();
+        let create_ata = missing.then(|| {
+            spl_associated_token_account::create_associated_token_account(
+                &owner, &owner, &mint,
+            )
+        });
+
+        let accounts = vec![
+            AccountMeta::new(
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farm, &mint,
+                ),
+                false,
+            ),
+            AccountMeta::new(farmer_ata, false),
+        ];
+
+        Ok((accounts, create_ata))
+    }
+
+    pub fn claim_rewards(&self, farm: Pubkey) -> Result<()> {
+        let owner = self.payer.pubkey();
+        let farmer = pda::find_farmer_address(farm, owner);
+        let farm_account: Farm = self.get_farm(farm)?;
+        let reward_mint = farm_account.reward.mint;
+
+        let accs = accounts::ClaimRewards {
+            farm,
+            farmer,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            reward_mint,
+            farm_reward_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farm, &reward_mint,
+                ),
+            farmer_reward_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &owner, &reward_mint,
+                ),
+            authority: owner,
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let mut request = self.program.request().accounts(accs);
+
+        let (secondary_accounts, create_ata) =
+            self.secondary_reward_accounts(farm)?;
+        if let Some(create_ata) = create_ata {
+            request = request.instruction(create_ata);
+        }
+        request = request.accounts(secondary_accounts);
+
+        // Stakes whose whitelist changed its rates follow, to move them to the
+        // new rates on the way.
+        for (stake_receipt, receipt) in self
+            .get_outdated_stakes(farmer)?
+            .into_iter()
+            .take(CLAIM_REFRESH_STAKES)
+        {
+            request = request.accounts(vec![
+                AccountMeta::new(stake_receipt, false),
+                AccountMeta::new_readonly(receipt.whitelist, false),
+                AccountMeta::new_readonly(
+                    pda::find_buff_proof_address(farm, receipt.mint),
+                    false,
+                ),
+            ]);
+        }
+
+        let signature = request
+            .args(dskullys_staking::instruction::ClaimRewards)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!("Claimed rewards. Signature: {}", signature);
+
+        Ok(())
+    }
+
+    /// Finds the whitelist proof a gem can be staked with, checking its mint,
+    /// its merkle proof and then its metadata's creator and collection.
+    pub(super) fn find_gem_whitelist(
+        &self,
+        farm: Pubkey,
+        gem_mint: &Pubkey,
+        merkle_proof: &[[u8; 32]],
+    ) -> Result<Pubkey> {
+        let is_whitelisted = |address: &Pubkey, ty: WhitelistType| {
+            let proof = pda::find_whitelist_proof_address(farm, *address);
+            self.program
+                .account::<WhitelistProof>(proof)
+                .ok()
+                .filter(|wl| wl.ty == ty)
+                .map(|_| proof)
+        };
+
+        if let Some(proof) = is_whitelisted(gem_mint, WhitelistType::Mint) {
+            return Ok(proof);
+        }
+
+        if !merkle_proof.is_empty() {
+            let root = merkle::root_address_of(gem_mint, merkle_proof);
+            if let Some(proof) = is_whitelisted(&root, WhitelistType::MerkleRoot)
+            {
+                return Ok(proof);
+            }
+        }
+
+        if let Ok(data) = self
+            .program
+            .rpc()
+            .get_account_data(&pda::find_metadata_address(gem_mint))
+        {
+            let metadata = parse_metadata(&data)?;
+
+            for ty in [WhitelistType::Creator, WhitelistType::Collection] {
+                if let Some(proof) = metadata_whitelist_key(&metadata, ty)
+                    .and_then(|address| is_whitelisted(&address, ty))
+                {
+                    return Ok(proof);
+                }
+            }
+        }
+
+        Err(anyhow!("{} is not whitelisted in farm {}", gem_mint, farm))
+    }
+}
@@ -0,0 +1,185 @@
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{bail, Context, Result};
+use dskullys_staking::merkle::{hash_pair, leaf};
+use std::{collections::HashMap, path::Path};
+
+/// Merkle tree of whitelisted mints, hashed the same way the program verifies
+/// `WhitelistType::MerkleRoot` proofs.
+pub struct MerkleTree {
+    // Leaves first, root last.
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    pub fn new(mints: &[Pubkey]) -> Result<Self> {
+        let mut leaves: Vec<_> = mints.iter().map(leaf).collect();
+        leaves.sort_unstable();
+        leaves.dedup();
+
+        if leaves.is_empty() {
+            bail!("Cannot build a merkle tree without mints.");
+        }
+
+        let mut layers = vec![leaves];
+
+        while layers.last().map_or(false, |layer| layer.len() > 1) {
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    // Odd nodes are promoted to the next layer as they are.
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+
+            layers.push(next);
+        }
+
+        Ok(Self { layers })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// The root as the address that gets whitelisted.
+    pub fn root_address(&self) -> Pubkey {
+        Pubkey::new_from_array(self.root())
+    }
+
+    pub fn proof(&self, mint: &Pubkey) -> Option<Vec<[u8; 32]>> {
+        let mut index = self.layers[0].binary_search(&leaf(mint)).ok()?;
+        let mut proof = Vec::with_capacity(self.layers.len());
+
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+
+        Some(proof)
+    }
+
+    /// Proofs for every given mint, encoded as base58 strings so they can be
+    /// shared with the frontend.
+    pub fn proofs(&self, mints: &[Pubkey]) -> HashMap<String, Vec<String>> {
+        mints
+            .iter()
+            .filter_map(|mint| {
+                let proof = self.proof(mint)?;
+                let encoded = proof
+                    .into_iter()
+                    .map(|node| Pubkey::new_from_array(node).to_string())
+                    .collect();
+                Some((mint.to_string(), encoded))
+            })
+            .collect()
+    }
+}
+
+/// Reads a file with one base58 mint address per line.
+pub fn read_mints(path: impl AsRef<Path>) -> Result<Vec<Pubkey>> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.parse::<Pubkey>()
+                .with_context(|| format!("Invalid mint address: {line}"))
+        })
+        .collect()
+}
+
+/// Reads the proofs written by `MerkleTree::proofs`.
+pub fn read_proofs(
+    path: impl AsRef<Path>,
+) -> Result<HashMap<Pubkey, Vec<[u8; 32]>>> {
+    let path = path.as_ref();
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let encoded: HashMap<String, Vec<String>> = serde_json::from_reader(file)?;
+
+    encoded
+        .into_iter()
+        .map(|(mint, proof)| {
+            let mint = mint.parse::<Pubkey>()?;
+            let proof = proof
+                .iter()
+                .map(|node| Ok(node.parse::<Pubkey>()?.to_bytes()))
+                .collect::<Result<Vec<_>>>()?;
+            Ok((mint, proof))
+        })
+        .collect()
+}
+
+/// The root a proof resolves to for the given mint, i.e. the address of the
+/// merkle root whitelist the mint belongs to.
+pub fn root_address_of(mint: &Pubkey, proof: &[[u8; 32]]) -> Pubkey {
+    let root = proof
+        .iter()
+        .fold(leaf(mint), |node, sibling| hash_pair(&node, sibling));
+
+    Pubkey::new_from_array(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dskullys_staking::merkle::verify;
+
+    fn mints(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn every_mint_has_a_proof_the_program_accepts() {
+        // Odd counts promote nodes without a sibling on some layers.
+        for count in [1, 2, 3, 5, 7, 8, 13] {
+            let mints = mints(count);
+            let tree = MerkleTree::new(&mints).unwrap();
+
+            for mint in &mints {
+                let proof = tree.proof(mint).unwrap();
+
+                assert!(
+                    verify(&proof, &tree.root(), leaf(mint)),
+                    "{count} mints"
+                );
+                assert_eq!(
+                    root_address_of(mint, &proof),
+                    tree.root_address()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn single_mint_trees_are_their_own_root() {
+        let mint = Pubkey::new_unique();
+        let tree = MerkleTree::new(&[mint]).unwrap();
+
+        assert_eq!(tree.root(), leaf(&mint));
+        assert!(tree.proof(&mint).unwrap().is_empty());
+        assert!(verify(&[], &tree.root(), leaf(&mint)));
+    }
+
+    #[test]
+    fn other_mints_are_rejected() {
+        let mints = mints(5);
+        let tree = MerkleTree::new(&mints).unwrap();
+        let other = Pubkey::new_unique();
+
+        assert!(tree.proof(&other).is_none());
+
+        let proof = tree.proof(&mints[0]).unwrap();
+        assert!(!verify(&proof, &tree.root(), leaf(&other)));
+    }
+}
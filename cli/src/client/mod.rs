@@ -1,23 +1,90 @@
 use anchor_client::{
     anchor_lang::{solana_program, system_program},
+    solana_sdk::instruction::AccountMeta,
     solana_sdk::{
-        commitment_config::CommitmentConfig, pubkey::Pubkey, signer::Signer,
+        commitment_config::CommitmentConfig, pubkey::Pubkey,
+        signature::Signature, signer::Signer,
     },
     Cluster, Program,
 };
+use anchor_spl::token::TokenAccount;
 use anyhow::{Context, Result};
 use dskullys_staking::{
     accounts,
-    state::{Farm, FarmManager, WhitelistProof, WhitelistType},
+    state::{
+        AccrualPolicy, BuffProof, Farm, FarmManager, FarmState, Farmer, StakeReceipt,
+        WhitelistProof, WhitelistType,
+    },
+};
+use solana_transaction_status::UiTransactionEncoding;
+use std::{
+    collections::HashMap,
+    rc::Rc,
 };
-use std::rc::Rc;
 
+use events::StakingEvent;
+use merkle::MerkleTree;
 use pda::*;
 use utils::*;
 
+mod batch;
+pub mod events;
+mod farmer;
+pub mod merkle;
 mod pda;
+mod secondary_reward;
+mod upgrade;
 mod utils;
 
+/// Most accounts `getMultipleAccounts` returns in one request.
+const MULTIPLE_ACCOUNTS_LIMIT: usize = 100;
+
+/// Settings of a new whitelist entry.
+#[derive(Debug, Clone, Copy)]
+pub struct WhitelistConfig {
+    /// Tokens/sec the farmers receive for every staked token.
+    pub reward_rate: u64,
+    /// Secondary reward tokens/sec the farmers receive for every staked token.
+    pub secondary_reward_rate: u64,
+    /// Seconds a gem must stay staked before it can be unstaked.
+    pub min_stake_duration: u64,
+    /// Seconds between beginning and finishing an unstake.
+    pub unstake_cooldown: u32,
+}
+
+/// Snapshot of a farm's rewards and staking activity.
+#[derive(Debug)]
+pub struct FarmStats {
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub state: FarmState,
+    pub vault_balance: u64,
+    pub available: u64,
+    pub reserved: u64,
+    pub farmers: usize,
+    /// Stake receipts holding gems, including the ones cooling down.
+    pub active_stakes: usize,
+    /// Whitelisted address, whitelist type and amount staked through it.
+    pub staked_per_whitelist: Vec<(Pubkey, WhitelistType, u64)>,
+    /// Staked amount that couldn't be matched to a whitelist entry, e.g. gems
+    /// staked through a removed whitelist, or through a merkle root whitelist
+    /// before receipts stored their whitelist.
+    pub unattributed_staked: u64,
+    /// Reward tokens emitted per second by all the running stakes.
+    pub emission_rate: u64,
+}
+
+impl FarmStats {
+    /// Days until the available rewards run out at the current emission rate.
+    pub fn runway_days(&self) -> Option<f64> {
+        if self.emission_rate == 0 || !self.state.emits_rewards() {
+            return None;
+        }
+
+        Some(self.available as f64 / self.emission_rate as f64 / 86_400.0)
+    }
+}
+
 pub struct StakingClient {
     program: Program,
     payer: Rc<dyn Signer>,
@@ -37,6 +104,10 @@ impl StakingClient {
         })
     }
 
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
     pub fn get_manager_farms(
         &self,
         manager_wallet: Option<Pubkey>,
@@ -69,9 +140,231 @@ impl StakingClient {
         find_accounts::<FarmManager>(&self.program, &[memcmp(8, farm.as_ref())])
     }
 
-    pub fn farm_stats(&self, farm: Pubkey) -> Result<()> {
-        let _farm: Farm = self.program.account(farm)?;
-        todo!("farm stats")
+    pub fn get_farm_farmers(
+        &self,
+        farm: Pubkey,
+    ) -> Result<Vec<(Pubkey, Farmer)>> {
+        find_accounts(&self.program, &[memcmp(8, farm.as_ref())])
+    }
+
+    /// Every stake receipt (running or not) of every farmer in the farm.
+    pub fn get_farm_stake_receipts(
+        &self,
+        farm: Pubkey,
+    ) -> Result<Vec<(Pubkey, StakeReceipt)>> {
+        let farmers = self.get_farm_farmers(farm)?;
+        self.get_farmers_stake_receipts(&farmers)
+    }
+
+    /// Stake receipts of the given farmers. Receipts don't store their farm,
+    /// so they're filtered by farmer, one request each.
+    fn get_farmers_stake_receipts(
+        &self,
+        farmers: &[(Pubkey, Farmer)],
+    ) -> Result<Vec<(Pubkey, StakeReceipt)>> {
+        let mut receipts = vec![];
+        for (farmer, _) in farmers {
+            receipts.extend(self.get_farmer_stake_receipts(*farmer)?);
+        }
+
+        Ok(receipts)
+    }
+
+    pub fn get_farm_buffs(
+        &self,
+        farm: Pubkey,
+    ) -> Result<Vec<(Pubkey, BuffProof)>> {
+        find_accounts(&self.program, &[memcmp(8, farm.as_ref())])
+    }
+
+    pub fn get_farm(&self, farm: Pubkey) -> Result<Farm> {
+        self.program
+            .account(farm)
+            .with_context(|| format!("Farm {} not found", farm))
+    }
+
+    /// Current balance of the farm's reward vault.
+    pub fn get_reward_vault_balance(
+        &self,
+        farm: Pubkey,
+        farm_account: &Farm,
+    ) -> Result<u64> {
+        let farm_vault =
+            anchor_spl::associated_token::get_associated_token_address(
+                &farm,
+                &farm_account.reward.mint,
+            );
+
+        let vault: TokenAccount = self
+            .program
+            .account(farm_vault)
+            .with_context(|| format!("Reward vault {} not found", farm_vault))?;
+
+        Ok(vault.amount)
+    }
+
+    /// Staking events emitted by a confirmed transaction.
+    pub fn get_transaction_events(
+        &self,
+        signature: &Signature,
+    ) -> Result<Vec<StakingEvent>> {
+        let transaction = self
+            .program
+            .rpc()
+            .get_transaction(signature, UiTransactionEncoding::Json)
+            .with_context(|| format!("Transaction {} not found", signature))?;
+
+        let logs = transaction
+            .transaction
+            .meta
+            .and_then(|meta| meta.log_messages)
+            .unwrap_or_default();
+
+        events::parse_logs(&logs)
+    }
+
+    pub fn farm_stats(&self, farm: Pubkey) -> Result<FarmStats> {
+        let farm_account = self.get_farm(farm)?;
+        let vault_balance = self.get_reward_vault_balance(farm, &farm_account)?;
+        let farmers = self.get_farm_farmers(farm)?;
+        let whitelists = self.get_farm_whitelists(farm)?;
+
+        // Farmers keep the rate of all their running stakes.
+        let emission_rate = farmers.iter().fold(0u64, |rate, (_, farmer)| {
+            rate.saturating_add(farmer.total_reward_rate())
+        });
+
+        // Receipts are only needed for the amounts staked per whitelist, and
+        // to count the stakes of farms that don't count them yet.
+        let receipts = self.get_farmers_stake_receipts(&farmers)?;
+        let active_stakes = if farm_account.stakes_counted {
+            farm_account.active_stakes as usize
+        } else {
+            receipts
+                .iter()
+                .filter(|(_, receipt)| {
+                    receipt.is_running() || receipt.is_cooling_down()
+                })
+                .count()
+        };
+        let running: Vec<_> = receipts
+            .into_iter()
+            .filter(|(_, receipt)| receipt.is_running())
+            .collect();
+
+        let mut staked: HashMap<Pubkey, u64> = HashMap::new();
+        let mut unattributed_staked = 0u64;
+        let receipt_whitelists =
+            self.find_receipt_whitelists(&running, &whitelists)?;
+
+        for ((_, receipt), whitelist) in running.iter().zip(receipt_whitelists)
+        {
+            match whitelist {
+                Some(address) => {
+                    let total = staked.entry(address).or_default();
+                    *total = total.saturating_add(receipt.amount);
+                }
+                None => {
+                    unattributed_staked =
+                        unattributed_staked.saturating_add(receipt.amount)
+                }
+            }
+        }
+
+        let staked_per_whitelist = whitelists
+            .iter()
+            .map(|(_, wl)| {
+                let amount = staked
+                    .get(&wl.whitelisted_address)
+                    .copied()
+                    .unwrap_or_default();
+                (wl.whitelisted_address, wl.ty, amount)
+            })
+            .collect();
+
+        Ok(FarmStats {
+            farm,
+            reward_mint: farm_account.reward.mint,
+            state: farm_account.state,
+            vault_balance,
+            available: farm_account.reward.available,
+            reserved: farm_account.reward.reserved,
+            farmers: farmers.len(),
+            active_stakes,
+            staked_per_whitelist,
+            unattributed_staked,
+            emission_rate,
+        })
+    }
+
+    /// The whitelisted address each stake receipt was staked through. Receipts
+    /// made before they stored their whitelist are matched by mint, then by
+    /// metadata, merkle root whitelists can't be resolved for them.
+    fn find_receipt_whitelists(
+        &self,
+        receipts: &[(Pubkey, StakeReceipt)],
+        whitelists: &[(Pubkey, WhitelistProof)],
+    ) -> Result<Vec<Option<Pubkey>>> {
+        let by_proof: HashMap<Pubkey, Pubkey> = whitelists
+            .iter()
+            .map(|(proof, wl)| (*proof, wl.whitelisted_address))
+            .collect();
+        let is_whitelisted = |address: &Pubkey, ty: WhitelistType| {
+            whitelists.iter().any(|(_, wl)| {
+                wl.ty == ty && wl.whitelisted_address == *address
+            })
+        };
+
+        let mut found = Vec::with_capacity(receipts.len());
+        let mut legacy = vec![];
+
+        for (i, (_, receipt)) in receipts.iter().enumerate() {
+            if receipt.is_migrated() {
+                found.push(by_proof.get(&receipt.whitelist).copied());
+            } else if is_whitelisted(&receipt.mint, WhitelistType::Mint) {
+                found.push(Some(receipt.mint));
+            } else {
+                found.push(None);
+                legacy.push(i);
+            }
+        }
+
+        let uses_metadata = whitelists.iter().any(|(_, wl)| {
+            matches!(wl.ty, WhitelistType::Creator | WhitelistType::Collection)
+        });
+
+        if !uses_metadata {
+            return Ok(found);
+        }
+
+        for batch in legacy.chunks(MULTIPLE_ACCOUNTS_LIMIT) {
+            let metadata_addresses: Vec<_> = batch
+                .iter()
+                .map(|&i| find_metadata_address(&receipts[i].1.mint))
+                .collect();
+            let accounts = self
+                .program
+                .rpc()
+                .get_multiple_accounts(&metadata_addresses)?;
+
+            // Fungible tokens usually don't have a metadata account.
+            for (&i, account) in batch.iter().zip(accounts) {
+                let metadata = match account {
+                    Some(account) => parse_metadata(&account.data)?,
+                    None => continue,
+                };
+
+                found[i] = [WhitelistType::Creator, WhitelistType::Collection]
+                    .into_iter()
+                    .filter_map(|ty| {
+                        metadata_whitelist_key(&metadata, ty)
+                            .filter(|address| is_whitelisted(address, ty))
+                    })
+                    .next();
+            }
+        }
+
+        Ok(found)
     }
 
     pub fn create_farm(&self, reward_mint: Pubkey) -> Result<Pubkey> {
@@ -111,16 +404,134 @@ impl StakingClient {
         self.program
             .request()
             .accounts(accs)
-            .args(dskullys_staking::instruction::AddManager)
+            .args(dskullys_staking::instruction::AddManager {
+                permissions: FarmManager::ALL,
+            })
             .send()?;
 
         Ok(farm)
     }
 
+    pub fn set_accrual_policy(
+        &self,
+        farm: Pubkey,
+        policy: AccrualPolicy,
+    ) -> Result<()> {
+        let accs = accounts::SetAccrualPolicy {
+            farm,
+            authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::SetAccrualPolicy { policy })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Set farm {} accrual policy to {:?}. Signature: {}",
+            farm, policy, signature
+        );
+
+        Ok(())
+    }
+
+    pub fn set_farm_state(&self, farm: Pubkey, state: FarmState) -> Result<()> {
+        let accs = accounts::SetFarmState {
+            farm,
+            authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::SetFarmState { state })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Set farm {} state to {:?}. Signature: {}",
+            farm, state, signature
+        );
+
+        Ok(())
+    }
+
+    /// Sets the farm's reward window, keeping its current start when
+    /// `start_ts` is `None`.
+    pub fn set_reward_window(
+        &self,
+        farm: Pubkey,
+        start_ts: Option<u64>,
+        end_ts: u64,
+    ) -> Result<()> {
+        let start_ts = match start_ts {
+            Some(start_ts) => start_ts,
+            None => self.get_farm(farm)?.reward_start_ts,
+        };
+
+        let accs = accounts::SetRewardWindow {
+            farm,
+            authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::SetRewardWindow {
+                start_ts,
+                end_ts,
+            })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Farm {} rewards run from {} to {}. Signature: {}",
+            farm, start_ts, end_ts, signature
+        );
+
+        Ok(())
+    }
+
+    pub fn extend_reward_window(
+        &self,
+        farm: Pubkey,
+        end_ts: u64,
+    ) -> Result<()> {
+        let farm_manager =
+            pda::find_farm_manager_address(farm, self.payer.pubkey());
+
+        let accs = accounts::ExtendRewardWindow {
+            farm,
+            farm_manager,
+            authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::ExtendRewardWindow { end_ts })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Farm {} rewards extended until {}. Signature: {}",
+            farm, end_ts, signature
+        );
+
+        Ok(())
+    }
+
     pub fn add_manager(
         &self,
         farm: Pubkey,
         manager_owner: Option<Pubkey>,
+        permissions: u8,
     ) -> Result<()> {
         let manager_owner =
             manager_owner.unwrap_or_else(|| self.payer.pubkey());
@@ -138,11 +549,11 @@ impl StakingClient {
             .program
             .request()
             .accounts(accs)
-            .args(dskullys_staking::instruction::AddManager)
+            .args(dskullys_staking::instruction::AddManager { permissions })
             .signer(&*self.payer)
             .send()?;
 
-        println!(
+        eprintln!(
             "Manager added to farm {}. Signature: {signature}.",
             farm_manager
         );
@@ -150,12 +561,127 @@ impl StakingClient {
         Ok(())
     }
 
+    pub fn remove_manager(
+        &self,
+        farm: Pubkey,
+        manager_owner: Pubkey,
+    ) -> Result<()> {
+        let farm_manager = find_farm_manager_address(farm, manager_owner);
+
+        let accs = accounts::RemoveManager {
+            farm,
+            farm_manager,
+            manager_authority: manager_owner,
+            authority: self.payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::RemoveManager)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Manager {} removed from farm. Signature: {signature}.",
+            farm_manager
+        );
+
+        Ok(())
+    }
+
+    pub fn propose_authority(
+        &self,
+        farm: Pubkey,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let accs = accounts::ProposeAuthority {
+            farm,
+            authority_transfer: find_authority_transfer_address(&farm),
+            new_authority,
+            authority: self.payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::ProposeAuthority)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Proposed {} as the authority of farm {}. Signature: {}",
+            new_authority, farm, signature
+        );
+
+        Ok(())
+    }
+
+    pub fn accept_authority(&self, farm: Pubkey) -> Result<()> {
+        let farm_account = self.get_farm(farm)?;
+
+        let accs = accounts::AcceptAuthority {
+            farm,
+            authority_transfer: find_authority_transfer_address(&farm),
+            previous_farm_manager: pda::find_farm_manager_address(
+                farm,
+                farm_account.authority,
+            ),
+            previous_authority: farm_account.authority,
+            new_authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::AcceptAuthority)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "{} is now the authority of farm {}. Signature: {}",
+            self.payer.pubkey(),
+            farm,
+            signature
+        );
+
+        Ok(())
+    }
+
+    pub fn cancel_authority_transfer(&self, farm: Pubkey) -> Result<()> {
+        let accs = accounts::CancelAuthorityTransfer {
+            farm,
+            authority_transfer: find_authority_transfer_address(&farm),
+            authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::CancelAuthorityTransfer)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Cancelled the authority transfer of farm {}. Signature: {}",
+            farm, signature
+        );
+
+        Ok(())
+    }
+
     pub fn add_to_whitelist(
         &self,
         farm: Pubkey,
         address_to_whitelist: Pubkey,
         whitelist_type: WhitelistType,
-        reward_rate: u64,
+        config: WhitelistConfig,
     ) -> Result<()> {
         let farm_manager =
             pda::find_farm_manager_address(farm, self.payer.pubkey());
@@ -177,7 +703,10 @@ impl StakingClient {
             .accounts(accs)
             .args(dskullys_staking::instruction::AddToWhitelist {
                 whitelist_type,
-                reward_rate,
+                reward_rate: config.reward_rate,
+                min_stake_duration: config.min_stake_duration,
+                unstake_cooldown: config.unstake_cooldown,
+                secondary_reward_rate: config.secondary_reward_rate,
             })
             .signer(&*self.payer)
             .send()?;
@@ -185,7 +714,7 @@ impl StakingClient {
         let whitelist_account: WhitelistProof =
             self.program.account(whitelist_proof)?;
 
-        println!(
+        eprintln!(
             "Added {} to whitelist. Signature: {}",
             whitelist_account.whitelisted_address, tx_sig
         );
@@ -193,6 +722,221 @@ impl StakingClient {
         Ok(())
     }
 
+    /// Whitelists every mint in `mints` by storing their merkle root in the farm
+    /// whitelist. The returned tree is needed to build the proofs when staking.
+    pub fn add_merkle_whitelist(
+        &self,
+        farm: Pubkey,
+        mints: &[Pubkey],
+        config: WhitelistConfig,
+    ) -> Result<MerkleTree> {
+        let tree = MerkleTree::new(mints)?;
+
+        self.add_to_whitelist(
+            farm,
+            tree.root_address(),
+            WhitelistType::MerkleRoot,
+            config,
+        )?;
+
+        Ok(tree)
+    }
+
+    pub fn update_whitelist(
+        &self,
+        farm: Pubkey,
+        address: Pubkey,
+        reward_rate: u64,
+        secondary_reward_rate: Option<u64>,
+    ) -> Result<()> {
+        let farm_manager =
+            pda::find_farm_manager_address(farm, self.payer.pubkey());
+        let whitelist_proof = pda::find_whitelist_proof_address(farm, address);
+
+        let secondary_reward_rate = match secondary_reward_rate {
+            Some(rate) => rate,
+            None => {
+                let whitelist: WhitelistProof = self
+                    .program
+                    .account(whitelist_proof)
+                    .with_context(|| format!("{} is not whitelisted", address))?;
+                whitelist.secondary_reward_rate
+            }
+        };
+
+        let accs = accounts::UpdateWhitelist {
+            farm,
+            farm_manager,
+            whitelist_proof,
+            authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::UpdateWhitelist {
+                reward_rate,
+                secondary_reward_rate,
+            })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Updated {} reward rates to {} and {}. Signature: {}",
+            address, reward_rate, secondary_reward_rate, signature
+        );
+
+        Ok(())
+    }
+
+    /// Moves every running stake of the whitelist to its current reward rate.
+    /// Stakes made before receipts stored their whitelist are matched to it
+    /// first, for merkle root whitelists this needs the proofs of the staked
+    /// mints.
+    pub fn migrate_whitelist(
+        &self,
+        farm: Pubkey,
+        address: Pubkey,
+        proofs: &HashMap<Pubkey, Vec<[u8; 32]>>,
+    ) -> Result<usize> {
+        let whitelist_proof = pda::find_whitelist_proof_address(farm, address);
+        let whitelist: WhitelistProof = self
+            .program
+            .account(whitelist_proof)
+            .with_context(|| format!("{} is not whitelisted", address))?;
+
+        let mut migrated = 0;
+
+        for (_, receipt) in self.get_farm_stake_receipts(farm)? {
+            if receipt.is_migrated() {
+                if receipt.whitelist == whitelist_proof
+                    && receipt.is_running()
+                    && receipt.rate_epoch < whitelist.rate_epoch
+                {
+                    self.refresh_stake(farm, &receipt)?;
+                    migrated += 1;
+                }
+
+                continue;
+            }
+
+            if !receipt.is_running() && !receipt.is_cooling_down() {
+                continue;
+            }
+
+            let merkle_proof = match whitelist.ty {
+                WhitelistType::Mint if receipt.mint == address => vec![],
+                WhitelistType::MerkleRoot => match proofs.get(&receipt.mint) {
+                    Some(proof) => proof.clone(),
+                    None => continue,
+                },
+                WhitelistType::Creator | WhitelistType::Collection => {
+                    let metadata = self
+                        .program
+                        .rpc()
+                        .get_account_data(&find_metadata_address(&receipt.mint))?;
+                    let metadata = parse_metadata(&metadata)?;
+
+                    if metadata_whitelist_key(&metadata, whitelist.ty)
+                        != Some(address)
+                    {
+                        continue;
+                    }
+
+                    vec![]
+                }
+                _ => continue,
+            };
+
+            self.migrate_stake(farm, &receipt, whitelist_proof, merkle_proof)?;
+
+            migrated += 1;
+        }
+
+        Ok(migrated)
+    }
+
+    pub fn refresh_stake(
+        &self,
+        farm: Pubkey,
+        receipt: &StakeReceipt,
+    ) -> Result<()> {
+        let accs = accounts::RefreshStake {
+            farm,
+            farmer: receipt.farmer,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            stake_receipt: pda::find_stake_receipt_address(
+                receipt.farmer,
+                receipt.mint,
+            ),
+            whitelist_proof: receipt.whitelist,
+            buff_proof: pda::find_buff_proof_address(farm, receipt.mint),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::RefreshStake)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Refreshed stake of {}. Signature: {}",
+            receipt.mint, signature
+        );
+
+        Ok(())
+    }
+
+    /// Records the whitelist of a stake made before receipts stored it, and
+    /// moves the stake to that whitelist's current rates.
+    pub fn migrate_stake(
+        &self,
+        farm: Pubkey,
+        receipt: &StakeReceipt,
+        whitelist_proof: Pubkey,
+        merkle_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let accs = accounts::MigrateStake {
+            farm,
+            farm_manager: pda::find_farm_manager_address(
+                farm,
+                self.payer.pubkey(),
+            ),
+            farmer: receipt.farmer,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            gem_mint: receipt.mint,
+            stake_receipt: pda::find_stake_receipt_address(
+                receipt.farmer,
+                receipt.mint,
+            ),
+            whitelist_proof,
+            buff_proof: pda::find_buff_proof_address(farm, receipt.mint),
+            authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .accounts(AccountMeta::new_readonly(
+                find_metadata_address(&receipt.mint),
+                false,
+            ))
+            .args(dskullys_staking::instruction::MigrateStake { merkle_proof })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Migrated stake of {}. Signature: {}",
+            receipt.mint, signature
+        );
+
+        Ok(())
+    }
+
     pub fn remove_from_whitelist(
         &self,
         farm: Pubkey,
@@ -218,7 +962,7 @@ impl StakingClient {
             .signer(&*self.payer)
             .send()?;
 
-        println!(
+        eprintln!(
             "Removed {} from whitelist. Signature: {}",
             address, signature
         );
@@ -226,6 +970,118 @@ impl StakingClient {
         Ok(())
     }
 
+    pub fn add_buff(
+        &self,
+        farm: Pubkey,
+        mint: Pubkey,
+        factor: u64,
+    ) -> Result<()> {
+        let farm_manager =
+            pda::find_farm_manager_address(farm, self.payer.pubkey());
+        let buff_proof = pda::find_buff_proof_address(farm, mint);
+
+        let accs = accounts::AddBuff {
+            farm,
+            farm_manager,
+            buff_proof,
+            mint,
+            authority: self.payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::AddBuff { factor })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!("Added buff to {}. Signature: {}", mint, signature);
+
+        Ok(())
+    }
+
+    pub fn remove_buff(&self, farm: Pubkey, mint: Pubkey) -> Result<()> {
+        let farm_manager =
+            pda::find_farm_manager_address(farm, self.payer.pubkey());
+        let buff_proof = pda::find_buff_proof_address(farm, mint);
+
+        let accs = accounts::RemoveBuff {
+            farm,
+            farm_manager,
+            buff_proof,
+            authority: self.payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::RemoveBuff)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!("Removed buff from {}. Signature: {}", mint, signature);
+
+        Ok(())
+    }
+
+    pub fn force_unstake(
+        &self,
+        farm: Pubkey,
+        owner: Pubkey,
+        gem_mint: Pubkey,
+    ) -> Result<()> {
+        let farm_manager =
+            pda::find_farm_manager_address(farm, self.payer.pubkey());
+        let farmer = pda::find_farmer_address(farm, owner);
+        let stake_receipt = pda::find_stake_receipt_address(farmer, gem_mint);
+
+        let farmer_vault =
+            anchor_spl::associated_token::get_associated_token_address(
+                &farmer, &gem_mint,
+            );
+
+        let gem_owner_ata =
+            anchor_spl::associated_token::get_associated_token_address(
+                &owner, &gem_mint,
+            );
+
+        let accs = accounts::ForceUnstake {
+            farm,
+            farm_manager,
+            farmer,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            gem_mint,
+            stake_receipt,
+            farmer_vault,
+            gem_owner_ata,
+            owner,
+            authority: self.payer.pubkey(),
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::ForceUnstake)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Force unstaked {} from {}. Signature: {}",
+            gem_mint, owner, signature
+        );
+
+        Ok(())
+    }
+
     pub fn deposit_reward(&self, farm: Pubkey, amount: u64) -> Result<()> {
         let farm_manager =
             pda::find_farm_manager_address(farm, self.payer.pubkey());
@@ -264,11 +1120,57 @@ impl StakingClient {
             .signer(&*self.payer)
             .send()?;
 
-        println!(
+        eprintln!(
             "Deposited {} to farm {}. Signature: {}",
             amount, farm, signature
         );
 
         Ok(())
     }
+
+    pub fn withdraw_reward(&self, farm: Pubkey, amount: u64) -> Result<()> {
+        let farm_account: Farm = self
+            .program
+            .account(farm)
+            .with_context(|| format!("Farm {} not found", farm))?;
+
+        let farm_vault =
+            anchor_spl::associated_token::get_associated_token_address(
+                &farm,
+                &farm_account.reward.mint,
+            );
+
+        let authority_ata =
+            anchor_spl::associated_token::get_associated_token_address(
+                &self.payer.pubkey(),
+                &farm_account.reward.mint,
+            );
+
+        let accs = accounts::WithdrawReward {
+            farm,
+            mint: farm_account.reward.mint,
+            farm_vault,
+            authority_ata,
+            authority: self.payer.pubkey(),
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::WithdrawReward { amount })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Withdrew {} from farm {}. Signature: {}",
+            amount, farm, signature
+        );
+
+        Ok(())
+    }
 }
@@ -22,6 +22,14 @@ pub fn find_farm_manager_address<T: AsRef<[u8]>>(
     .0
 }
 
+pub fn find_authority_transfer_address(farm: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"authority_transfer", farm.as_ref()],
+        &dskullys_staking::id(),
+    )
+    .0
+}
+
 pub fn find_whitelist_proof_address<T: AsRef<[u8]>>(
     farm: T,
     address: T,
@@ -33,7 +41,22 @@ pub fn find_whitelist_proof_address<T: AsRef<[u8]>>(
     .0
 }
 
-#[allow(dead_code)]
+pub fn find_buff_proof_address<T: AsRef<[u8]>>(farm: T, mint: T) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"buff_proof", farm.as_ref(), mint.as_ref()],
+        &dskullys_staking::id(),
+    )
+    .0
+}
+
+pub fn find_farmer_address<T: AsRef<[u8]>>(farm: T, owner: T) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"farmer", farm.as_ref(), owner.as_ref()],
+        &dskullys_staking::id(),
+    )
+    .0
+}
+
 pub fn find_stake_receipt_address<T: AsRef<[u8]>>(
     farmer: T,
     mint: T,
@@ -44,3 +67,23 @@ pub fn find_stake_receipt_address<T: AsRef<[u8]>>(
     )
     .0
 }
+
+pub fn find_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+    .0
+}
+
+pub fn find_secondary_reward_address(farm: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"secondary_reward", farm.as_ref()],
+        &dskullys_staking::id(),
+    )
+    .0
+}
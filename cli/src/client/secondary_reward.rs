@@ -0,0 +1,198 @@
+use anchor_client::{
+    anchor_lang::{solana_program, system_program},
+    solana_sdk::pubkey::Pubkey,
+};
+use anyhow::{Context, Result};
+use dskullys_staking::{accounts, state::SecondaryReward};
+
+use super::{pda, StakingClient};
+
+impl StakingClient {
+    /// The farm's secondary reward, if it has one.
+    pub fn get_secondary_reward(
+        &self,
+        farm: Pubkey,
+    ) -> Result<Option<SecondaryReward>> {
+        let address = pda::find_secondary_reward_address(&farm);
+
+        match self.program.rpc().get_account(&address) {
+            Ok(_) => Ok(Some(self.program.account(address)?)),
+            Err(_) => Ok(None),
+        }
+    }
+
+    fn secondary_reward_mint(&self, farm: Pubkey) -> Result<Pubkey> {
+        let secondary_reward = self
+            .get_secondary_reward(farm)?
+            .with_context(|| format!("Farm {} has no secondary reward", farm))?;
+
+        Ok(secondary_reward.reward.mint)
+    }
+
+    pub fn add_secondary_reward(
+        &self,
+        farm: Pubkey,
+        reward_mint: Pubkey,
+    ) -> Result<()> {
+        let accs = accounts::AddSecondaryReward {
+            farm,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            reward_mint,
+            farm_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farm,
+                    &reward_mint,
+                ),
+            authority: self.payer.pubkey(),
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::AddSecondaryReward)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Farm {} now also rewards {}. Signature: {}",
+            farm, reward_mint, signature
+        );
+
+        Ok(())
+    }
+
+    pub fn deposit_secondary_reward(
+        &self,
+        farm: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let mint = self.secondary_reward_mint(farm)?;
+
+        let accs = accounts::FundSecondaryReward {
+            farm,
+            farm_manager: pda::find_farm_manager_address(
+                farm,
+                self.payer.pubkey(),
+            ),
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            mint,
+            farm_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farm, &mint,
+                ),
+            manager_ata:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &self.payer.pubkey(),
+                    &mint,
+                ),
+            authority: self.payer.pubkey(),
+            token_program: anchor_spl::token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::FundSecondaryReward { amount })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Deposited {} secondary reward tokens to farm {}. Signature: {}",
+            amount, farm, signature
+        );
+
+        Ok(())
+    }
+
+    pub fn withdraw_secondary_reward(
+        &self,
+        farm: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let mint = self.secondary_reward_mint(farm)?;
+
+        let accs = accounts::WithdrawSecondaryReward {
+            farm,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            mint,
+            farm_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farm, &mint,
+                ),
+            authority_ata:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &self.payer.pubkey(),
+                    &mint,
+                ),
+            authority: self.payer.pubkey(),
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::WithdrawSecondaryReward {
+                amount,
+            })
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Withdrew {} secondary reward tokens from farm {}. Signature: {}",
+            amount, farm, signature
+        );
+
+        Ok(())
+    }
+
+    /// Sends what's left of the secondary reward to the authority and stops
+    /// paying it.
+    pub fn close_secondary_reward(&self, farm: Pubkey) -> Result<()> {
+        let mint = self.secondary_reward_mint(farm)?;
+
+        let accs = accounts::CloseSecondaryReward {
+            farm,
+            secondary_reward: pda::find_secondary_reward_address(&farm),
+            mint,
+            farm_vault:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &farm, &mint,
+                ),
+            authority_ata:
+                anchor_spl::associated_token::get_associated_token_address(
+                    &self.payer.pubkey(),
+                    &mint,
+                ),
+            authority: self.payer.pubkey(),
+            rent: solana_program::sysvar::rent::ID,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::CloseSecondaryReward)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Closed the secondary reward of farm {}. Signature: {}",
+            farm, signature
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use anchor_client::{
+    anchor_lang::{system_program, Discriminator},
+    solana_client::{
+        rpc_config::RpcProgramAccountsConfig, rpc_filter::RpcFilterType,
+    },
+    solana_sdk::{instruction::Instruction, pubkey::Pubkey},
+};
+use anyhow::Result;
+use dskullys_staking::{
+    accounts,
+    state::{FarmManager, WhitelistProof, WhitelistType},
+};
+use std::collections::HashMap;
+
+use super::{
+    utils::{instruction, memcmp},
+    StakingClient,
+};
+
+/// Accounts migrated per transaction, each one only adds its own address.
+const MIGRATE_BATCH_ACCOUNTS: usize = 10;
+
+impl StakingClient {
+    /// Moves the farm's accounts created by older versions of the program to
+    /// their current layout, so they can be loaded again, and records the
+    /// whitelist of the stakes made before receipts stored it. Once no such
+    /// stake is left, the farm authority marks its stakes as counted.
+    pub fn upgrade_farm(&self, farm: Pubkey) -> Result<()> {
+        let whitelists = self
+            .find_legacy_accounts::<WhitelistProof>(
+                WhitelistProof::LEGACY_LEN,
+                40,
+                farm,
+            )?
+            .into_iter()
+            .map(|whitelist_proof| {
+                instruction(
+                    accounts::MigrateWhitelistProof {
+                        whitelist_proof,
+                        payer: self.payer.pubkey(),
+                        system_program: system_program::ID,
+                    },
+                    dskullys_staking::instruction::MigrateWhitelistProof,
+                )
+            })
+            .collect();
+
+        self.send_migration_batches(whitelists, "whitelists")?;
+
+        let managers = self
+            .find_legacy_accounts::<FarmManager>(
+                FarmManager::LEGACY_LEN,
+                8,
+                farm,
+            )?
+            .into_iter()
+            .map(|farm_manager| {
+                instruction(
+                    accounts::MigrateFarmManager {
+                        farm_manager,
+                        payer: self.payer.pubkey(),
+                        system_program: system_program::ID,
+                    },
+                    dskullys_staking::instruction::MigrateFarmManager,
+                )
+            })
+            .collect();
+
+        self.send_migration_batches(managers, "managers")?;
+
+        // Stakes made before receipts stored their whitelist. Merkle root
+        // whitelists need the proofs, they're migrated by `farm wl update`.
+        let mut stakes = 0;
+        for (_, whitelist) in self.get_farm_whitelists(farm)? {
+            if whitelist.ty != WhitelistType::MerkleRoot {
+                stakes += self.migrate_whitelist(
+                    farm,
+                    whitelist.whitelisted_address,
+                    &HashMap::new(),
+                )?;
+            }
+        }
+
+        eprintln!("Migrated {} stakes.", stakes);
+
+        let farm_account = self.get_farm(farm)?;
+        if farm_account.stakes_counted {
+            return Ok(());
+        }
+
+        let legacy = self
+            .get_farm_stake_receipts(farm)?
+            .into_iter()
+            .filter(|(_, receipt)| {
+                !receipt.is_migrated()
+                    && (receipt.is_running() || receipt.is_cooling_down())
+            })
+            .count();
+
+        if legacy > 0 {
+            eprintln!("{} stakes still need to be migrated.", legacy);
+        } else if farm_account.authority == self.payer.pubkey() {
+            self.finish_stake_migration(farm)?;
+        } else {
+            eprintln!("The farm authority must upgrade the farm to finish.");
+        }
+
+        Ok(())
+    }
+
+    /// Lets the farm rely on its count of active stakes, once every stake
+    /// made before it was counted has been migrated.
+    pub fn finish_stake_migration(&self, farm: Pubkey) -> Result<()> {
+        let accs = accounts::FinishStakeMigration {
+            farm,
+            authority: self.payer.pubkey(),
+        };
+
+        let signature = self
+            .program
+            .request()
+            .accounts(accs)
+            .args(dskullys_staking::instruction::FinishStakeMigration)
+            .signer(&*self.payer)
+            .send()?;
+
+        eprintln!(
+            "Finished migrating the stakes of farm {}. Signature: {}",
+            farm, signature
+        );
+
+        Ok(())
+    }
+
+    /// Accounts of type `T` still in a legacy layout of `legacy_len` bytes,
+    /// whose farm is stored at `farm_offset`.
+    fn find_legacy_accounts<T: Discriminator>(
+        &self,
+        legacy_len: usize,
+        farm_offset: usize,
+        farm: Pubkey,
+    ) -> Result<Vec<Pubkey>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(8 + legacy_len as u64),
+                memcmp(0, &T::discriminator()),
+                memcmp(farm_offset, farm.as_ref()),
+            ]),
+            ..Default::default()
+        };
+
+        let accounts = self
+            .program
+            .rpc()
+            .get_program_accounts_with_config(&dskullys_staking::ID, config)?;
+
+        Ok(accounts.into_iter().map(|(address, _)| address).collect())
+    }
+
+    fn send_migration_batches(
+        &self,
+        instructions: Vec<Instruction>,
+        accounts_name: &str,
+    ) -> Result<()> {
+        if instructions.is_empty() {
+            eprintln!("No {} to migrate.", accounts_name);
+        }
+
+        for batch in instructions.chunks(MIGRATE_BATCH_ACCOUNTS) {
+            let mut request = self.program.request();
+            for ix in batch {
+                request = request.instruction(ix.clone());
+            }
+
+            let signature = request.signer(&*self.payer).send()?;
+
+            eprintln!(
+                "Migrated {} {}. Signature: {}",
+                batch.len(),
+                accounts_name,
+                signature
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,17 @@
 use anchor_client::{
-    anchor_lang::{AccountDeserialize, Discriminator},
+    anchor_lang::{
+        AccountDeserialize, Discriminator, InstructionData, ToAccountMetas,
+    },
     solana_client::rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
-    solana_sdk::pubkey::Pubkey,
+    solana_sdk::{
+        borsh::try_from_slice_unchecked, instruction::Instruction,
+        pubkey::Pubkey,
+    },
     Program, ProgramAccountsIterator,
 };
 use anyhow::Context;
+use dskullys_staking::state::WhitelistType;
+use mpl_token_metadata::state::Metadata;
 
 pub fn memcmp(offset: usize, bytes: &[u8]) -> RpcFilterType {
     RpcFilterType::Memcmp(Memcmp {
@@ -37,3 +44,42 @@ pub fn find_accounts_lazy<T: Discriminator + AccountDeserialize>(
         .map_err(anyhow::Error::new)
         .context("failed to get accounts")
 }
+
+/// A staking program instruction, for transactions that send more than one.
+pub fn instruction(
+    accounts: impl ToAccountMetas,
+    args: impl InstructionData,
+) -> Instruction {
+    Instruction {
+        program_id: dskullys_staking::ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+pub fn parse_metadata(data: &[u8]) -> anyhow::Result<Metadata> {
+    try_from_slice_unchecked(data).context("failed to parse metadata account")
+}
+
+/// The address a creator or collection whitelist must hold for this metadata
+/// to be accepted, mirroring the program's validation.
+pub fn metadata_whitelist_key(
+    metadata: &Metadata,
+    ty: WhitelistType,
+) -> Option<Pubkey> {
+    match ty {
+        WhitelistType::Creator => metadata
+            .data
+            .creators
+            .as_ref()?
+            .first()
+            .filter(|creator| creator.verified)
+            .map(|creator| creator.address),
+        WhitelistType::Collection => metadata
+            .collection
+            .as_ref()
+            .filter(|collection| collection.verified)
+            .map(|collection| collection.key),
+        WhitelistType::Mint | WhitelistType::MerkleRoot => None,
+    }
+}
@@ -28,4 +28,65 @@ pub enum StakingError {
 
     #[msg("An arithmetic error occurred.")]
     ArithmeticError,
+
+    #[msg("Insufficient available reward. Could not withdraw.")]
+    CouldNotWithdrawReward,
+
+    #[msg("Mint is not part of the whitelisted merkle tree.")]
+    InvalidMerkleProof,
+
+    #[msg("Invalid reward window.")]
+    InvalidRewardWindow,
+
+    #[msg("Farm manager does not have the required permissions.")]
+    MissingPermission,
+
+    #[msg("Invalid farm manager permissions.")]
+    InvalidPermissions,
+
+    #[msg("The secondary reward must be a different token than the farm reward.")]
+    InvalidSecondaryReward,
+
+    #[msg("Invalid secondary reward accounts.")]
+    InvalidSecondaryRewardAccounts,
+
+    #[msg("Partial unstakes must leave some tokens staked.")]
+    InvalidUnstakeAmount,
+
+    // No longer returned, kept so the codes after it don't change.
+    #[msg("Only SPL Token mints and non-programmable NFTs can be staked.")]
+    UnsupportedTokenStandard,
+
+    #[msg("Invalid accounts for the batch of gems.")]
+    InvalidBatchAccounts,
+
+    #[msg("Farmer still has staked gems or unclaimed rewards.")]
+    FarmerNotEmpty,
+
+    #[msg("Staking is paused on this farm.")]
+    StakingPaused,
+
+    #[msg("Farm is frozen.")]
+    FarmFrozen,
+
+    #[msg("Farm still has active stakes, reserved rewards or a secondary reward.")]
+    FarmNotEmpty,
+
+    #[msg("Account already uses the current layout.")]
+    AccountAlreadyMigrated,
+
+    #[msg("Stake was made through another whitelist.")]
+    WhitelistMismatch,
+
+    #[msg("Stake already uses the whitelist's current rates.")]
+    StakeUpToDate,
+
+    #[msg("Farm still has stakes made before they were counted.")]
+    StakesNotCounted,
+
+    #[msg("Farmers still earn or are owed secondary rewards.")]
+    SecondaryRewardOwed,
+
+    #[msg("Farm is still open.")]
+    FarmNotClosed,
 }
@@ -0,0 +1,241 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AccrualPolicy, FarmState, WhitelistType};
+
+#[event]
+#[derive(Debug)]
+pub struct FarmCreated {
+    pub farm: Pubkey,
+    pub authority: Pubkey,
+    pub reward_mint: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ManagerAdded {
+    pub farm: Pubkey,
+    pub manager: Pubkey,
+    pub permissions: u8,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ManagerRemoved {
+    pub farm: Pubkey,
+    pub manager: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct Whitelisted {
+    pub farm: Pubkey,
+    pub whitelisted_address: Pubkey,
+    pub ty: WhitelistType,
+    pub reward_rate: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct Staked {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub reward_rate: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct Unstaked {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct UnstakeBegun {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub cooldown_end_ts: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct StakeRefreshed {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub reward_rate: u64,
+    pub secondary_reward_rate: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RewardsClaimed {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RewardFunded {
+    pub farm: Pubkey,
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RewardWithdrawn {
+    pub farm: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ForceUnstaked {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct FarmStateChanged {
+    pub farm: Pubkey,
+    pub state: FarmState,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AccrualPolicyChanged {
+    pub farm: Pubkey,
+    pub policy: AccrualPolicy,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RewardWindowChanged {
+    pub farm: Pubkey,
+    pub start_ts: u64,
+    pub end_ts: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct FarmClosed {
+    pub farm: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuthorityProposed {
+    pub farm: Pubkey,
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuthorityAccepted {
+    pub farm: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AuthorityTransferCancelled {
+    pub farm: Pubkey,
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct StakeMigrationFinished {
+    pub farm: Pubkey,
+    pub active_stakes: u32,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct WhitelistUpdated {
+    pub farm: Pubkey,
+    pub whitelisted_address: Pubkey,
+    pub reward_rate: u64,
+    pub secondary_reward_rate: u64,
+    pub rate_epoch: u32,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct WhitelistRemoved {
+    pub farm: Pubkey,
+    pub whitelisted_address: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct BuffAdded {
+    pub farm: Pubkey,
+    pub mint: Pubkey,
+    pub factor: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct BuffRemoved {
+    pub farm: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct SecondaryRewardAdded {
+    pub farm: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct SecondaryRewardClosed {
+    pub farm: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct FarmerInitialized {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct FarmerClosed {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct StakeReceiptClosed {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+}
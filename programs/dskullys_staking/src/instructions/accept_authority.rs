@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::AuthorityAccepted, state::*, utils};
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        has_one = new_authority,
+        close = previous_authority,
+        seeds = [AuthorityTransfer::PREFIX, farm.key().as_ref()],
+        bump,
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    /// CHECK: The previous authority's manager, which may not exist. Not deserialized so
+    /// managers created before permissions were added are closed too.
+    #[account(
+        mut,
+        seeds = [
+            FarmManager::PREFIX,
+            farm.key().as_ref(),
+            previous_authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub previous_farm_manager: UncheckedAccount<'info>,
+
+    /// CHECK: Gets the proposal and manager rent back.
+    #[account(mut, address = farm.authority)]
+    pub previous_authority: UncheckedAccount<'info>,
+
+    pub new_authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let new_authority = ctx.accounts.new_authority.key();
+
+    ctx.accounts.farm.transfer_authority(new_authority);
+
+    // The previous authority would otherwise keep every permission over the farm.
+    let previous_farm_manager = ctx.accounts.previous_farm_manager.to_account_info();
+    if previous_farm_manager.owner == ctx.program_id {
+        utils::close_account(
+            previous_farm_manager,
+            ctx.accounts.previous_authority.to_account_info(),
+        )?;
+    }
+
+    msg!("{} is the new farm authority", new_authority);
+
+    emit!(AuthorityAccepted {
+        farm: ctx.accounts.farm.key(),
+        previous_authority: ctx.accounts.previous_authority.key(),
+        new_authority,
+    });
+
+    Ok(())
+}
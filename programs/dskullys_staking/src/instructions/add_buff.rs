@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{error::StakingError, events::BuffAdded, state::*};
+
+#[derive(Accounts)]
+pub struct AddBuff<'info> {
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        has_one = authority,
+        constraint = farm_manager.can(FarmManager::RATES) @ StakingError::MissingPermission,
+        seeds = [
+            FarmManager::PREFIX,
+            farm.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub farm_manager: Account<'info, FarmManager>,
+
+    #[account(
+        init,
+        space = 8 + BuffProof::LEN,
+        payer = authority,
+        seeds = [
+            BuffProof::PREFIX,
+            farm.key().as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub buff_proof: Account<'info, BuffProof>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddBuff>, factor: u64) -> Result<()> {
+    require_gt!(factor, 0, StakingError::FactorMustBeGtZero);
+
+    *ctx.accounts.buff_proof = BuffProof {
+        farm: ctx.accounts.farm.key(),
+        mint: ctx.accounts.mint.key(),
+        factor,
+    };
+
+    emit!(BuffAdded {
+        farm: ctx.accounts.farm.key(),
+        mint: ctx.accounts.mint.key(),
+        factor,
+    });
+
+    Ok(())
+}
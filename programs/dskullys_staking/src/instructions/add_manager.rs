@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::*;
+use crate::{error::StakingError, events::ManagerAdded, state::*};
 
 #[derive(Accounts)]
 pub struct AddManager<'info> {
@@ -27,11 +27,23 @@ pub struct AddManager<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<AddManager>) -> Result<()> {
+pub fn handler(ctx: Context<AddManager>, permissions: u8) -> Result<()> {
+    require!(
+        permissions != 0 && permissions & !FarmManager::ALL == 0,
+        StakingError::InvalidPermissions
+    );
+
     *ctx.accounts.farm_manager = FarmManager {
         farm: ctx.accounts.farm.key(),
         authority: ctx.accounts.manager_authority.key(),
+        permissions,
     };
 
+    emit!(ManagerAdded {
+        farm: ctx.accounts.farm.key(),
+        manager: ctx.accounts.manager_authority.key(),
+        permissions,
+    });
+
     Ok(())
 }
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::AssociatedToken, token::*};
+
+use crate::{error::StakingError, events::SecondaryRewardAdded, state::*};
+
+#[derive(Accounts)]
+pub struct AddSecondaryReward<'info> {
+    #[account(has_one = authority)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SecondaryReward::LEN,
+        seeds = [SecondaryReward::PREFIX, farm.key().as_ref()],
+        bump
+    )]
+    pub secondary_reward: Account<'info, SecondaryReward>,
+
+    #[account(
+        constraint = reward_mint.key() != farm.reward.mint @ StakingError::InvalidSecondaryReward
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+    )]
+    pub farm_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<AddSecondaryReward>) -> Result<()> {
+    *ctx.accounts.secondary_reward = SecondaryReward {
+        farm: ctx.accounts.farm.key(),
+        reward: Reward::new(ctx.accounts.reward_mint.key()),
+        bump: [*ctx.bumps.get("secondary_reward").unwrap()],
+        earning_rate: 0,
+    };
+
+    msg!(
+        "Farm {} now also rewards {}",
+        ctx.accounts.farm.key(),
+        ctx.accounts.reward_mint.key()
+    );
+
+    emit!(SecondaryRewardAdded {
+        farm: ctx.accounts.farm.key(),
+        mint: ctx.accounts.reward_mint.key(),
+    });
+
+    Ok(())
+}
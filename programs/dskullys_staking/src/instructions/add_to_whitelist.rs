@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 
-use crate::state::*;
+use crate::{error::StakingError, events::Whitelisted, state::*};
 
 #[derive(Accounts)]
 pub struct AddToWhitelist<'info> {
@@ -9,6 +9,7 @@ pub struct AddToWhitelist<'info> {
 
     #[account(
         has_one = authority,
+        constraint = farm_manager.can(FarmManager::WHITELIST) @ StakingError::MissingPermission,
         seeds = [
             FarmManager::PREFIX,
             farm.key().as_ref(),
@@ -31,7 +32,7 @@ pub struct AddToWhitelist<'info> {
     )]
     pub whitelist_proof: Account<'info, WhitelistProof>,
 
-    /// CHECK: Collection creator or mint address.
+    /// CHECK: Collection creator, collection mint, mint address or merkle root.
     pub creator_or_mint: UncheckedAccount<'info>,
 
     #[account(mut)]
@@ -44,18 +45,32 @@ pub fn handler(
     ctx: Context<AddToWhitelist>,
     reward_rate: u64,
     whitelist_type: WhitelistType,
+    min_stake_duration: u64,
+    unstake_cooldown: u32,
+    secondary_reward_rate: u64,
 ) -> Result<()> {
-    if let WhitelistType::Mint = whitelist_type {
+    // Both fungible tokens and verified collections are identified by a mint.
+    if let WhitelistType::Mint | WhitelistType::Collection = whitelist_type {
         let data = ctx.accounts.creator_or_mint.try_borrow_mut_data()?;
         Mint::try_deserialize(&mut &**data)?;
     }
 
-    *ctx.accounts.whitelist_proof = WhitelistProof {
+    *ctx.accounts.whitelist_proof = WhitelistProof::new(
+        ctx.accounts.creator_or_mint.key(),
+        ctx.accounts.farm.key(),
         reward_rate,
-        ty: whitelist_type,
+        secondary_reward_rate,
+        whitelist_type,
+        min_stake_duration,
+        unstake_cooldown,
+    );
+
+    emit!(Whitelisted {
         farm: ctx.accounts.farm.key(),
         whitelisted_address: ctx.accounts.creator_or_mint.key(),
-    };
+        ty: whitelist_type,
+        reward_rate,
+    });
 
     Ok(())
 }
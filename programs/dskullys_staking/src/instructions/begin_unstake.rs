@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::error::StakingError;
+use crate::events::UnstakeBegun;
+use crate::state::*;
+use crate::utils::now_ts;
+
+#[derive(Accounts)]
+pub struct BeginUnstake<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        has_one = owner,
+        seeds = [
+            Farmer::PREFIX,
+            farm.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    pub farmer: Account<'info, Farmer>,
+
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(address = stake_receipt.mint)]
+    pub gem_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = farmer,
+        seeds = [
+            StakeReceipt::PREFIX,
+            farmer.key().as_ref(),
+            gem_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub stake_receipt: Account<'info, StakeReceipt>,
+
+    pub owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<BeginUnstake>) -> Result<()> {
+    let now = now_ts()?;
+    let receipt = &mut ctx.accounts.stake_receipt;
+
+    require!(receipt.is_running(), StakingError::GemNotStaked);
+    receipt.require_unlocked(now)?;
+
+    // The gems stop earning rewards during the cooldown.
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+    ctx.accounts.farmer.end_stake(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+        receipt,
+        now,
+    )?;
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        ctx.accounts.farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
+    receipt.start_cooldown(now);
+
+    msg!("Gems can be unstaked after {}", receipt.cooldown_end_ts);
+
+    emit!(UnstakeBegun {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.owner.key(),
+        mint: receipt.mint,
+        amount: receipt.amount,
+        cooldown_end_ts: receipt.cooldown_end_ts,
+    });
+
+    Ok(())
+}
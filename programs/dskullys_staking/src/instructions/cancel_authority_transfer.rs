@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::AuthorityTransferCancelled, state::*};
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(has_one = authority)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        close = authority,
+        seeds = [AuthorityTransfer::PREFIX, farm.key().as_ref()],
+        bump,
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    let new_authority = ctx.accounts.authority_transfer.new_authority;
+
+    msg!("Cancelled the transfer to {}", new_authority);
+
+    emit!(AuthorityTransferCancelled {
+        farm: ctx.accounts.farm.key(),
+        authority: ctx.accounts.authority.key(),
+        new_authority,
+    });
+
+    Ok(())
+}
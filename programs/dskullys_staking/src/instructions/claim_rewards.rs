@@ -1,19 +1,29 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{get_associated_token_address, AssociatedToken},
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{state::*, utils};
+use crate::{
+    error::StakingError, events::RewardsClaimed, instructions::refresh_stakes, state::*, utils,
+};
 
+/// Remaining accounts: the secondary reward vaults when the farm has one, followed by
+/// `[stake_receipt, whitelist_proof, buff_proof]` for each stake to move to its whitelist's
+/// current rates.
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
-    #[account(mut)]
+    #[account(mut, constraint = farm.state.can_claim() @ StakingError::FarmFrozen)]
     pub farm: Account<'info, Farm>,
 
     #[account(mut, has_one = farm)]
     pub farmer: Account<'info, Farmer>,
 
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
     #[account(address = farm.reward.mint)]
     pub reward_mint: Account<'info, Mint>,
 
@@ -54,12 +64,102 @@ impl<'info> ClaimRewards<'info> {
     }
 }
 
-pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
-    let reward = ctx.accounts.farmer.claim_accrued(&mut ctx.accounts.farm)?;
+/// Pays the farmer's accrued secondary rewards, which must have been updated first. `accounts`
+/// are the farm's vault of the secondary reward and the owner's token account for it.
+pub fn claim_secondary_reward<'info>(
+    farm: &Account<'info, Farm>,
+    farmer: &mut Account<'info, Farmer>,
+    secondary_reward: &mut SecondaryReward,
+    accounts: &[AccountInfo<'info>],
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let (farm_vault, farmer_vault) = match accounts {
+        [farm_vault, farmer_vault, ..] => (farm_vault, farmer_vault),
+        _ => return err!(StakingError::InvalidSecondaryRewardAccounts),
+    };
+
+    let mint = secondary_reward.reward.mint;
+
+    require_keys_eq!(
+        farm_vault.key(),
+        get_associated_token_address(&farm.key(), &mint),
+        StakingError::InvalidSecondaryRewardAccounts
+    );
+
+    let farmer_token_account: Account<TokenAccount> = Account::try_from(farmer_vault)?;
+
+    require_keys_eq!(
+        farmer_token_account.mint,
+        mint,
+        StakingError::InvalidSecondaryRewardAccounts
+    );
+    require_keys_eq!(
+        farmer_token_account.owner,
+        farmer.owner,
+        StakingError::InvalidSecondaryRewardAccounts
+    );
+
+    let amount = farmer.claim_secondary_accrued(&mut secondary_reward.reward)?;
+
+    let cpi_ctx = utils::transfer_spl_ctx(
+        farm_vault.clone(),
+        farmer_vault.clone(),
+        farm.to_account_info(),
+        token_program,
+    );
+    anchor_spl::token::transfer(cpi_ctx.with_signer(&[&farm.seeds()]), amount)?;
+
+    msg!("Claimed {} secondary reward tokens", amount);
+
+    emit!(RewardsClaimed {
+        farm: farm.key(),
+        owner: farmer.owner,
+        mint,
+        amount,
+    });
+
+    Ok(())
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimRewards<'info>>) -> Result<()> {
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+    let reward = ctx.accounts.farmer.claim_accrued(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+    )?;
 
     ctx.accounts.transfer_reward(reward)?;
 
     msg!("Claimed {} tokens", reward);
 
+    emit!(RewardsClaimed {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.authority.key(),
+        mint: ctx.accounts.reward_mint.key(),
+        amount: reward,
+    });
+
+    // Farms with a secondary reward expect its vaults to be passed as remaining accounts.
+    let mut stakes = ctx.remaining_accounts;
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        claim_secondary_reward(
+            &ctx.accounts.farm,
+            &mut ctx.accounts.farmer,
+            secondary_reward,
+            stakes,
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+        stakes = &stakes[2..];
+    }
+
+    // Rewards were settled by the claim, the stakes can switch rates.
+    refresh_stakes(&ctx.accounts.farm, &mut ctx.accounts.farmer, stakes)?;
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        ctx.accounts.farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
     Ok(())
 }
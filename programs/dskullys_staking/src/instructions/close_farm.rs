@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{error::StakingError, events::FarmClosed, state::*, utils};
+
+/// Shuts the farm down once nothing is staked nor owed, sending what's left in the reward vault
+/// to the farm authority. Whitelists, buffs, managers and the secondary reward must be closed
+/// first, and farms older than the stake counter must have finished migrating their stakes.
+#[derive(Accounts)]
+pub struct CloseFarm<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority,
+        constraint = farm.stakes_counted @ StakingError::StakesNotCounted,
+        constraint = farm.active_stakes == 0 @ StakingError::FarmNotEmpty,
+        constraint = farm.reward.reserved == 0 @ StakingError::FarmNotEmpty,
+    )]
+    pub farm: Account<'info, Farm>,
+
+    /// CHECK: Only checked to be closed, so its vault isn't left behind.
+    #[account(
+        constraint = secondary_reward.data_is_empty() @ StakingError::FarmNotEmpty,
+        seeds = [SecondaryReward::PREFIX, farm.key().as_ref()],
+        bump,
+    )]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(address = farm.reward.mint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+    )]
+    pub farm_reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = reward_mint,
+        associated_token::authority = authority,
+    )]
+    pub authority_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<CloseFarm>) -> Result<()> {
+    // Sweep the whole balance, including tokens sent to the vault without funding the farm.
+    let amount = ctx.accounts.farm_reward_vault.amount;
+    let farm_seeds = ctx.accounts.farm.seeds();
+
+    let cpi_ctx = utils::transfer_spl_ctx(
+        ctx.accounts.farm_reward_vault.to_account_info(),
+        ctx.accounts.authority_ata.to_account_info(),
+        ctx.accounts.farm.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+    anchor_spl::token::transfer(cpi_ctx.with_signer(&[&farm_seeds]), amount)?;
+
+    utils::close_ata(
+        ctx.accounts.farm_reward_vault.to_account_info(),
+        ctx.accounts.farm.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        Some(&farm_seeds),
+    )?;
+
+    msg!(
+        "Closed farm {}, swept {} tokens",
+        ctx.accounts.farm.key(),
+        amount
+    );
+
+    emit!(FarmClosed {
+        farm: ctx.accounts.farm.key(),
+        authority: ctx.accounts.authority.key(),
+        amount,
+    });
+
+    Ok(())
+}
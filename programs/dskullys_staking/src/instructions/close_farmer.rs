@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakingError, events::FarmerClosed, state::*};
+
+/// Returns the farmer's rent once it has nothing staked nor owed. Gems that are still cooling
+/// down stay reachable, since initializing the farmer again recreates the same account.
+#[derive(Accounts)]
+pub struct CloseFarmer<'info> {
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = farm,
+        has_one = owner,
+        constraint = farmer.is_empty() @ StakingError::FarmerNotEmpty,
+        seeds = [
+            Farmer::PREFIX,
+            farm.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    pub farmer: Account<'info, Farmer>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseFarmer>) -> Result<()> {
+    msg!("Closed farmer {}", ctx.accounts.farmer.key());
+
+    emit!(FarmerClosed {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{error::StakingError, events::SecondaryRewardClosed, state::*, utils};
+
+/// Stops paying the secondary reward, sending what's left in its vault to the farm authority.
+/// Farmers are only settled when they interact, so nothing may still earn it: whitelists must
+/// stop paying it and every stake be moved to their new rates, then farmers must have claimed
+/// what they accrued.
+#[derive(Accounts)]
+pub struct CloseSecondaryReward<'info> {
+    #[account(has_one = authority)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = farm,
+        constraint = secondary_reward.earning_rate == 0 @ StakingError::SecondaryRewardOwed,
+        constraint = secondary_reward.reward.reserved == 0 @ StakingError::SecondaryRewardOwed,
+    )]
+    pub secondary_reward: Account<'info, SecondaryReward>,
+
+    #[account(address = secondary_reward.reward.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = farm,
+    )]
+    pub farm_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+    )]
+    pub authority_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<CloseSecondaryReward>) -> Result<()> {
+    let amount = ctx.accounts.farm_vault.amount;
+    let farm_seeds = ctx.accounts.farm.seeds();
+
+    let cpi_ctx = utils::transfer_spl_ctx(
+        ctx.accounts.farm_vault.to_account_info(),
+        ctx.accounts.authority_ata.to_account_info(),
+        ctx.accounts.farm.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+    anchor_spl::token::transfer(cpi_ctx.with_signer(&[&farm_seeds]), amount)?;
+
+    utils::close_ata(
+        ctx.accounts.farm_vault.to_account_info(),
+        ctx.accounts.farm.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        Some(&farm_seeds),
+    )?;
+
+    msg!("Closed the secondary reward, swept {} tokens", amount);
+
+    emit!(SecondaryRewardClosed {
+        farm: ctx.accounts.farm.key(),
+        authority: ctx.accounts.authority.key(),
+        amount,
+    });
+
+    Ok(())
+}
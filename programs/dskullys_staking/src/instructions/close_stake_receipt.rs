@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakingError, events::StakeReceiptClosed, state::*};
+
+/// Returns the rent of a receipt whose gems were unstaked. Staking the same mint again
+/// creates a new one.
+#[derive(Accounts)]
+pub struct CloseStakeReceipt<'info> {
+    #[account(has_one = owner)]
+    pub farmer: Account<'info, Farmer>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = farmer,
+        constraint = !stake_receipt.is_running() && !stake_receipt.is_cooling_down()
+            @ StakingError::GemStillStaked,
+    )]
+    pub stake_receipt: Account<'info, StakeReceipt>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseStakeReceipt>) -> Result<()> {
+    msg!("Closed stake receipt of {}", ctx.accounts.stake_receipt.mint);
+
+    emit!(StakeReceiptClosed {
+        farm: ctx.accounts.farmer.farm,
+        owner: ctx.accounts.owner.key(),
+        mint: ctx.accounts.stake_receipt.mint,
+    });
+
+    Ok(())
+}
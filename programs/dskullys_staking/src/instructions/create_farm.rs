@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token::*};
 
-use crate::state::*;
+use crate::{events::FarmCreated, state::*};
 
 #[derive(Accounts)]
 pub struct CreateFarm<'info> {
@@ -45,5 +45,11 @@ pub fn handler(ctx: Context<CreateFarm>) -> Result<()> {
 
     msg!("Initialized new farm at {}", ctx.accounts.farm.key());
 
+    emit!(FarmCreated {
+        farm: ctx.accounts.farm.key(),
+        authority: ctx.accounts.authority.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+    });
+
     Ok(())
 }
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakingError, events::RewardWindowChanged, state::*, utils::now_ts};
+
+#[derive(Accounts)]
+pub struct ExtendRewardWindow<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        has_one = authority,
+        constraint = farm_manager.can(FarmManager::RATES) @ StakingError::MissingPermission,
+        seeds = [
+            FarmManager::PREFIX,
+            farm.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub farm_manager: Account<'info, FarmManager>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ExtendRewardWindow>, end_ts: u64) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    // Extending a window that is already over would pay for the time the farm was closed,
+    // starting a new season is up to the farm authority.
+    if farm.reward_end_ts == 0 || farm.reward_end_ts < now_ts()? {
+        msg!("Reward window is unbounded or already over.");
+        return err!(StakingError::InvalidRewardWindow);
+    }
+
+    require_gt!(end_ts, farm.reward_end_ts, StakingError::InvalidRewardWindow);
+
+    farm.reward_end_ts = end_ts;
+
+    msg!("Farm rewards extended until {}", end_ts);
+
+    emit!(RewardWindowChanged {
+        farm: farm.key(),
+        start_ts: farm.reward_start_ts,
+        end_ts,
+    });
+
+    Ok(())
+}
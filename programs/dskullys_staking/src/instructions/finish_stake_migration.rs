@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakingError, events::StakeMigrationFinished, state::*};
+
+/// Marks the farm's active stakes as complete, once every stake made before the farm counted
+/// them was migrated with `migrate_stake`. Only then can the farm be closed. Stakes left out by
+/// finishing early can still be withdrawn with `withdraw_orphaned_stake` once the farm is closed.
+#[derive(Accounts)]
+pub struct FinishStakeMigration<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+        constraint = !farm.stakes_counted @ StakingError::AccountAlreadyMigrated,
+    )]
+    pub farm: Account<'info, Farm>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FinishStakeMigration>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    farm.stakes_counted = true;
+
+    msg!("Farm counts {} active stakes", farm.active_stakes);
+
+    emit!(StakeMigrationFinished {
+        farm: farm.key(),
+        active_stakes: farm.active_stakes,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::error::StakingError;
+use crate::events::ForceUnstaked;
+use crate::utils::{self, close_ata, now_ts};
+
+use crate::state::*;
+
+/// Unstakes a gem on behalf of its owner. Signed by the farm authority or a manager allowed to
+/// force unstakes.
+#[derive(Accounts)]
+pub struct ForceUnstake<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    /// CHECK: Not initialized when the farm authority signs, read through
+    /// `FarmManager::authorize`.
+    #[account(
+        seeds = [
+            FarmManager::PREFIX,
+            farm.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub farm_manager: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        has_one = owner,
+        seeds = [
+            Farmer::PREFIX,
+            farm.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    pub farmer: Account<'info, Farmer>,
+
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(address = stake_receipt.mint)]
+    pub gem_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = farmer,
+        seeds = [
+            StakeReceipt::PREFIX,
+            farmer.key().as_ref(),
+            gem_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub stake_receipt: Account<'info, StakeReceipt>,
+
+    #[account(
+        mut,
+        associated_token::mint = gem_mint,
+        associated_token::authority = farmer,
+    )]
+    pub farmer_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = gem_mint,
+        associated_token::authority = owner,
+    )]
+    pub gem_owner_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Receives the farmer vault's rent, which was paid when staking.
+    #[account(mut)]
+    pub owner: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> ForceUnstake<'info> {
+    pub fn release_gems(&self, amount: u64) -> Result<()> {
+        let cpi_ctx = utils::transfer_spl_ctx(
+            self.farmer_vault.to_account_info(),
+            self.gem_owner_ata.to_account_info(),
+            self.farmer.to_account_info(),
+            self.token_program.to_account_info(),
+        );
+
+        anchor_spl::token::transfer(
+            cpi_ctx.with_signer(&[&self.farmer.seeds()]),
+            amount,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<ForceUnstake>) -> Result<()> {
+    FarmManager::authorize(
+        &ctx.accounts.farm_manager,
+        &ctx.accounts.farm,
+        ctx.accounts.authority.key,
+        FarmManager::FORCE_UNSTAKE,
+    )?;
+
+    let now = now_ts()?;
+    let receipt = &mut ctx.accounts.stake_receipt;
+
+    // Locks and cooldowns don't apply to force unstakes.
+    if receipt.is_running() {
+        let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+        ctx.accounts.farmer.end_stake(
+            &mut ctx.accounts.farm,
+            secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+            receipt,
+            now,
+        )?;
+
+        if let Some(secondary_reward) = &mut secondary_reward {
+            ctx.accounts.farmer.sync_secondary_rate(secondary_reward)?;
+            secondary_reward.exit(ctx.program_id)?;
+        }
+    } else {
+        require!(receipt.is_cooling_down(), StakingError::GemNotStaked);
+        receipt.cooldown_end_ts = 0;
+    }
+
+    ctx.accounts.farm.remove_stake(receipt)?;
+
+    ctx.accounts.release_gems(ctx.accounts.stake_receipt.amount)?;
+
+    close_ata(
+        ctx.accounts.farmer_vault.to_account_info(),
+        ctx.accounts.farmer.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        Some(&ctx.accounts.farmer.seeds()),
+    )?;
+
+    msg!(
+        "Force unstaked {} tokens of {} from {} by {}",
+        ctx.accounts.stake_receipt.amount,
+        ctx.accounts.gem_mint.key(),
+        ctx.accounts.owner.key(),
+        ctx.accounts.authority.key()
+    );
+
+    emit!(ForceUnstaked {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.owner.key(),
+        mint: ctx.accounts.gem_mint.key(),
+        amount: ctx.accounts.stake_receipt.amount,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
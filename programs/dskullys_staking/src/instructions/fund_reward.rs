@@ -1,14 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::{state::*, utils};
+use crate::{error::StakingError, events::RewardFunded, state::*, utils};
 
 #[derive(Accounts)]
 pub struct FundReward<'info> {
     #[account(mut)]
     pub farm: Account<'info, Farm>,
 
-    #[account(has_one = authority, has_one = farm)]
+    #[account(
+        has_one = authority,
+        has_one = farm,
+        constraint = farm_manager.can(FarmManager::FUND) @ StakingError::MissingPermission,
+    )]
     pub farm_manager: Account<'info, FarmManager>,
 
     #[account(address = farm.reward.mint)]
@@ -45,5 +49,12 @@ pub fn handler(ctx: Context<FundReward>, amount: u64) -> Result<()> {
 
     ctx.accounts.farm.reward.try_fund(amount)?;
 
+    emit!(RewardFunded {
+        farm: ctx.accounts.farm.key(),
+        funder: ctx.accounts.authority.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
     Ok(())
 }
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{error::StakingError, events::RewardFunded, state::*, utils};
+
+#[derive(Accounts)]
+pub struct FundSecondaryReward<'info> {
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        has_one = authority,
+        has_one = farm,
+        constraint = farm_manager.can(FarmManager::FUND) @ StakingError::MissingPermission,
+    )]
+    pub farm_manager: Account<'info, FarmManager>,
+
+    #[account(mut, has_one = farm)]
+    pub secondary_reward: Account<'info, SecondaryReward>,
+
+    #[account(address = secondary_reward.reward.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = farm,
+    )]
+    pub farm_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+    )]
+    pub manager_ata: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<FundSecondaryReward>, amount: u64) -> Result<()> {
+    let cpi_ctx = utils::transfer_spl_ctx(
+        ctx.accounts.manager_ata.to_account_info(),
+        ctx.accounts.farm_vault.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    );
+
+    anchor_spl::token::transfer(cpi_ctx, amount)?;
+
+    ctx.accounts.secondary_reward.reward.try_fund(amount)?;
+
+    emit!(RewardFunded {
+        farm: ctx.accounts.farm.key(),
+        funder: ctx.accounts.authority.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::*;
+use crate::{events::FarmerInitialized, state::*, utils::now_ts};
 
 #[derive(Accounts)]
 pub struct InitializeFarmer<'info> {
@@ -28,7 +28,17 @@ pub struct InitializeFarmer<'info> {
 pub fn handler(ctx: Context<InitializeFarmer>) -> Result<()> {
     let bump = ctx.bumps.get("farmer").unwrap();
 
-    *ctx.accounts.farmer = Farmer::new(ctx.accounts.farm.key(), ctx.accounts.owner.key(), *bump)?;
+    *ctx.accounts.farmer = Farmer::new(
+        &ctx.accounts.farm,
+        ctx.accounts.owner.key(),
+        *bump,
+        now_ts()?,
+    );
+
+    emit!(FarmerInitialized {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.owner.key(),
+    });
 
     Ok(())
 }
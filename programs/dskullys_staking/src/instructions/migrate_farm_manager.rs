@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::*, utils};
+
+#[derive(Accounts)]
+pub struct MigrateFarmManager<'info> {
+    /// CHECK: Still in the legacy layout, which can't be deserialized. The discriminator and
+    /// length are checked by `utils::migrate_account`.
+    #[account(mut, owner = crate::ID)]
+    pub farm_manager: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves a manager created before permissions existed to the current layout, keeping all the
+/// permissions it had.
+pub fn handler(ctx: Context<MigrateFarmManager>) -> Result<()> {
+    let info = ctx.accounts.farm_manager.to_account_info();
+
+    utils::migrate_account::<FarmManager>(
+        info.clone(),
+        FarmManager::LEGACY_LEN,
+        FarmManager::LEN,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let mut farm_manager: Account<FarmManager> = Account::try_from(&info)?;
+    farm_manager.permissions = FarmManager::ALL;
+    farm_manager.exit(&crate::ID)
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{error::StakingError, events::StakeRefreshed, state::*};
+
+/// Records the whitelist of a stake made before receipts kept track of it, counts it in the
+/// farm's active stakes, then moves the stake to that whitelist's current rates.
+#[derive(Accounts)]
+pub struct MigrateStake<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        has_one = farm,
+        has_one = authority,
+        constraint = farm_manager.can(FarmManager::RATES) @ StakingError::MissingPermission,
+        seeds = [
+            FarmManager::PREFIX,
+            farm.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub farm_manager: Account<'info, FarmManager>,
+
+    #[account(mut, has_one = farm)]
+    pub farmer: Account<'info, Farmer>,
+
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(address = stake_receipt.mint)]
+    pub gem_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = farmer,
+        seeds = [
+            StakeReceipt::PREFIX,
+            farmer.key().as_ref(),
+            gem_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub stake_receipt: Account<'info, StakeReceipt>,
+
+    #[account(has_one = farm)]
+    pub whitelist_proof: Account<'info, WhitelistProof>,
+
+    /// CHECK: May not be initialized when the gem has no buff, read through
+    /// `BuffProof::factor_of`.
+    #[account(
+        seeds = [
+            BuffProof::PREFIX,
+            farm.key().as_ref(),
+            gem_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub buff_proof: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateStake<'info>>,
+    merkle_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let receipt = &ctx.accounts.stake_receipt;
+
+    require!(
+        receipt.is_running() || receipt.is_cooling_down(),
+        StakingError::GemNotStaked
+    );
+
+    WhitelistProof::validate(
+        &ctx.accounts.whitelist_proof,
+        &ctx.accounts.gem_mint,
+        ctx.program_id,
+        ctx.remaining_accounts,
+        &merkle_proof,
+    )?;
+
+    let whitelist = ctx.accounts.whitelist_proof.key();
+    let receipt = &mut ctx.accounts.stake_receipt;
+    receipt.migrate(whitelist)?;
+    // The farm only counts the stakes that store their whitelist.
+    ctx.accounts.farm.add_stake()?;
+
+    // Settle what was earned at the old rate before switching.
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+    let farmer = &mut ctx.accounts.farmer;
+    farmer.settle_accrued_rewards(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+    )?;
+    let refreshed = farmer.refresh_stake(
+        receipt,
+        whitelist,
+        &ctx.accounts.whitelist_proof,
+        BuffProof::factor_of(&ctx.accounts.buff_proof)?,
+    )?;
+
+    msg!("Stake of {} migrated to whitelist {}", receipt.mint, whitelist);
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
+    if refreshed {
+        emit!(StakeRefreshed {
+            farm: ctx.accounts.farm.key(),
+            owner: farmer.owner,
+            mint: receipt.mint,
+            reward_rate: receipt.reward_rate,
+            secondary_reward_rate: receipt.secondary_reward_rate,
+        });
+    }
+
+    Ok(())
+}
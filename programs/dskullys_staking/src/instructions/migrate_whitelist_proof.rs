@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{state::*, utils};
+
+#[derive(Accounts)]
+pub struct MigrateWhitelistProof<'info> {
+    /// CHECK: Still in the legacy layout, which can't be deserialized. The discriminator and
+    /// length are checked by `utils::migrate_account`.
+    #[account(mut, owner = crate::ID)]
+    pub whitelist_proof: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves a whitelist created before its layout grew to the current one. The new fields start
+/// zeroed: no minimum stake duration, no unstake cooldown and no secondary reward.
+pub fn handler(ctx: Context<MigrateWhitelistProof>) -> Result<()> {
+    utils::migrate_account::<WhitelistProof>(
+        ctx.accounts.whitelist_proof.to_account_info(),
+        WhitelistProof::LEGACY_LEN,
+        WhitelistProof::LEN,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )
+}
@@ -1,19 +1,81 @@
+pub mod accept_authority;
+pub mod add_buff;
 pub mod add_manager;
+pub mod add_secondary_reward;
 pub mod add_to_whitelist;
+pub mod begin_unstake;
+pub mod cancel_authority_transfer;
 pub mod claim_rewards;
+pub mod close_farm;
+pub mod close_farmer;
+pub mod close_secondary_reward;
+pub mod close_stake_receipt;
 pub mod create_farm;
+pub mod extend_reward_window;
+pub mod finish_stake_migration;
+pub mod force_unstake;
 pub mod fund_reward;
+pub mod fund_secondary_reward;
 pub mod initialize_farmer;
+pub mod migrate_farm_manager;
+pub mod migrate_stake;
+pub mod migrate_whitelist_proof;
+pub mod partial_unstake;
+pub mod propose_authority;
+pub mod refresh_stake;
+pub mod remove_buff;
 pub mod remove_from_whitelist;
+pub mod remove_manager;
+pub mod set_accrual_policy;
+pub mod set_farm_state;
+pub mod set_reward_window;
 pub mod stake;
+pub mod stake_many;
 pub mod unstake;
+pub mod unstake_and_claim;
+pub mod unstake_many;
+pub mod update_whitelist;
+pub mod withdraw_orphaned_stake;
+pub mod withdraw_reward;
+pub mod withdraw_secondary_reward;
 
+pub use accept_authority::*;
+pub use add_buff::*;
 pub use add_manager::*;
+pub use add_secondary_reward::*;
 pub use add_to_whitelist::*;
+pub use begin_unstake::*;
+pub use cancel_authority_transfer::*;
 pub use claim_rewards::*;
+pub use close_farm::*;
+pub use close_farmer::*;
+pub use close_secondary_reward::*;
+pub use close_stake_receipt::*;
 pub use create_farm::*;
+pub use extend_reward_window::*;
+pub use finish_stake_migration::*;
+pub use force_unstake::*;
 pub use fund_reward::*;
+pub use fund_secondary_reward::*;
 pub use initialize_farmer::*;
+pub use migrate_farm_manager::*;
+pub use migrate_stake::*;
+pub use migrate_whitelist_proof::*;
+pub use partial_unstake::*;
+pub use propose_authority::*;
+pub use refresh_stake::*;
+pub use remove_buff::*;
 pub use remove_from_whitelist::*;
+pub use remove_manager::*;
+pub use set_accrual_policy::*;
+pub use set_farm_state::*;
+pub use set_reward_window::*;
 pub use stake::*;
+pub use stake_many::*;
 pub use unstake::*;
+pub use unstake_and_claim::*;
+pub use unstake_many::*;
+pub use update_whitelist::*;
+pub use withdraw_orphaned_stake::*;
+pub use withdraw_reward::*;
+pub use withdraw_secondary_reward::*;
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::error::StakingError;
+use crate::events::Unstaked;
+use crate::utils::{self, now_ts};
+
+use crate::state::*;
+
+/// Takes part of a fungible stake out, the rest keeps earning rewards. When the whitelist changed
+/// its rates, its proof and the buff proof are passed as remaining accounts so the rest of the
+/// stake moves to the new rates.
+#[derive(Accounts)]
+pub struct PartialUnstake<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        has_one = owner,
+        seeds = [
+            Farmer::PREFIX,
+            farm.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    pub farmer: Account<'info, Farmer>,
+
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(address = stake_receipt.mint)]
+    pub gem_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = farmer,
+        seeds = [
+            StakeReceipt::PREFIX,
+            farmer.key().as_ref(),
+            gem_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub stake_receipt: Account<'info, StakeReceipt>,
+
+    #[account(
+        mut,
+        associated_token::mint = gem_mint,
+        associated_token::authority = farmer,
+    )]
+    pub farmer_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = gem_mint,
+        associated_token::authority = owner,
+    )]
+    pub gem_owner_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> PartialUnstake<'info> {
+    pub fn release_gems(&self, amount: u64) -> Result<()> {
+        let cpi_ctx = utils::transfer_spl_ctx(
+            self.farmer_vault.to_account_info(),
+            self.gem_owner_ata.to_account_info(),
+            self.farmer.to_account_info(),
+            self.token_program.to_account_info(),
+        );
+
+        anchor_spl::token::transfer(
+            cpi_ctx.with_signer(&[&self.farmer.seeds()]),
+            amount,
+        )
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PartialUnstake<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let now = now_ts()?;
+    let receipt = &ctx.accounts.stake_receipt;
+
+    require!(receipt.is_running(), StakingError::GemNotStaked);
+    receipt.require_unlocked(now)?;
+    // Gems with a cooldown can only leave through `begin_unstake`.
+    require!(
+        receipt.unstake_cooldown == 0,
+        StakingError::CooldownIsNotOver
+    );
+
+    // Settle what was earned at the current rate before lowering it.
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+    let farmer = &mut ctx.accounts.farmer;
+    farmer.settle_accrued_rewards(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+    )?;
+
+    let receipt = &mut ctx.accounts.stake_receipt;
+
+    if let [whitelist_proof, buff_proof] = ctx.remaining_accounts {
+        let whitelist_proof: Account<WhitelistProof> = Account::try_from(whitelist_proof)?;
+        let (buff_proof_key, _bump) = Pubkey::find_program_address(
+            &[
+                BuffProof::PREFIX,
+                ctx.accounts.farm.key().as_ref(),
+                receipt.mint.as_ref(),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(buff_proof.key(), buff_proof_key);
+
+        farmer.refresh_stake(
+            receipt,
+            whitelist_proof.key(),
+            &whitelist_proof,
+            BuffProof::factor_of(buff_proof)?,
+        )?;
+    }
+
+    farmer.decrease_reward_rate(receipt)?;
+    receipt.remove_amount(amount)?;
+    farmer.increase_reward_rate(receipt)?;
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
+    ctx.accounts.release_gems(amount)?;
+
+    msg!(
+        "Unstaked {} tokens, {} still staked",
+        amount,
+        ctx.accounts.stake_receipt.amount
+    );
+
+    emit!(Unstaked {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.owner.key(),
+        mint: ctx.accounts.gem_mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::AuthorityProposed, state::*};
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(has_one = authority)]
+    pub farm: Account<'info, Farm>,
+
+    // Proposing again replaces the previous proposal.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AuthorityTransfer::LEN,
+        seeds = [AuthorityTransfer::PREFIX, farm.key().as_ref()],
+        bump,
+    )]
+    pub authority_transfer: Account<'info, AuthorityTransfer>,
+
+    /// CHECK: Any address can become the farm authority, it must sign to accept it.
+    pub new_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ProposeAuthority>) -> Result<()> {
+    *ctx.accounts.authority_transfer = AuthorityTransfer {
+        farm: ctx.accounts.farm.key(),
+        new_authority: ctx.accounts.new_authority.key(),
+    };
+
+    msg!(
+        "Proposed {} as the new farm authority",
+        ctx.accounts.new_authority.key()
+    );
+
+    emit!(AuthorityProposed {
+        farm: ctx.accounts.farm.key(),
+        authority: ctx.accounts.authority.key(),
+        new_authority: ctx.accounts.new_authority.key(),
+    });
+
+    Ok(())
+}
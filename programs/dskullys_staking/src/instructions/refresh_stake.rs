@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakingError, events::StakeRefreshed, state::*};
+
+/// Moves a running stake to the current reward rate of the whitelist it was staked through.
+/// Anyone can call it, so the farm managers can migrate every stake after updating a whitelist.
+#[derive(Accounts)]
+pub struct RefreshStake<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(mut, has_one = farm)]
+    pub farmer: Account<'info, Farmer>,
+
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = farmer,
+        seeds = [
+            StakeReceipt::PREFIX,
+            farmer.key().as_ref(),
+            stake_receipt.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub stake_receipt: Account<'info, StakeReceipt>,
+
+    #[account(address = stake_receipt.whitelist @ StakingError::WhitelistMismatch)]
+    pub whitelist_proof: Account<'info, WhitelistProof>,
+
+    /// CHECK: May not be initialized when the gem has no buff, read through
+    /// `BuffProof::factor_of`.
+    #[account(
+        seeds = [
+            BuffProof::PREFIX,
+            farm.key().as_ref(),
+            stake_receipt.mint.as_ref(),
+        ],
+        bump,
+    )]
+    pub buff_proof: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<RefreshStake>) -> Result<()> {
+    require!(
+        ctx.accounts.stake_receipt.is_running(),
+        StakingError::GemNotStaked
+    );
+
+    // Settle what was earned at the old rate before switching.
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+    let farmer = &mut ctx.accounts.farmer;
+    farmer.settle_accrued_rewards(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+    )?;
+
+    let refreshed = farmer.refresh_stake(
+        &mut ctx.accounts.stake_receipt,
+        ctx.accounts.whitelist_proof.key(),
+        &ctx.accounts.whitelist_proof,
+        BuffProof::factor_of(&ctx.accounts.buff_proof)?,
+    )?;
+
+    require!(refreshed, StakingError::StakeUpToDate);
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
+    let receipt = &ctx.accounts.stake_receipt;
+
+    msg!("Stake reward rate refreshed to {}", receipt.reward_rate);
+
+    emit!(StakeRefreshed {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.farmer.owner,
+        mint: receipt.mint,
+        reward_rate: receipt.reward_rate,
+        secondary_reward_rate: receipt.secondary_reward_rate,
+    });
+
+    Ok(())
+}
+
+/// Refreshes the stakes passed as `[stake_receipt, whitelist_proof, buff_proof]` triples, so
+/// instructions touching a farmer's rewards pick up the whitelists' current rates on the way.
+/// The farmer's rewards must have been settled first.
+pub fn refresh_stakes<'info>(
+    farm: &Account<'info, Farm>,
+    farmer: &mut Account<'info, Farmer>,
+    accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    require!(
+        accounts.len() % 3 == 0,
+        StakingError::InvalidBatchAccounts
+    );
+
+    for triple in accounts.chunks_exact(3) {
+        let mut receipt: Account<StakeReceipt> = Account::try_from(&triple[0])?;
+        let whitelist_proof: Account<WhitelistProof> = Account::try_from(&triple[1])?;
+        let buff_proof = &triple[2];
+
+        require_keys_eq!(receipt.farmer, farmer.key(), StakingError::InvalidBatchAccounts);
+
+        let (buff_proof_key, _bump) = Pubkey::find_program_address(
+            &[
+                BuffProof::PREFIX,
+                farm.key().as_ref(),
+                receipt.mint.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(buff_proof.key(), buff_proof_key, StakingError::InvalidBatchAccounts);
+
+        if farmer.refresh_stake(
+            &mut receipt,
+            whitelist_proof.key(),
+            &whitelist_proof,
+            BuffProof::factor_of(buff_proof)?,
+        )? {
+            receipt.exit(&crate::ID)?;
+
+            emit!(StakeRefreshed {
+                farm: farm.key(),
+                owner: farmer.owner,
+                mint: receipt.mint,
+                reward_rate: receipt.reward_rate,
+                secondary_reward_rate: receipt.secondary_reward_rate,
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakingError, events::BuffRemoved, state::*};
+
+#[derive(Accounts)]
+pub struct RemoveBuff<'info> {
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        has_one = authority,
+        constraint = farm_manager.can(FarmManager::RATES) @ StakingError::MissingPermission,
+        seeds = [
+            FarmManager::PREFIX,
+            farm.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub farm_manager: Account<'info, FarmManager>,
+
+    #[account(mut, close = authority, has_one = farm)]
+    pub buff_proof: Account<'info, BuffProof>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RemoveBuff>) -> Result<()> {
+    msg!("Removed buff from {}!", ctx.accounts.buff_proof.mint);
+
+    emit!(BuffRemoved {
+        farm: ctx.accounts.farm.key(),
+        mint: ctx.accounts.buff_proof.mint,
+    });
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::state::*;
+use crate::{error::StakingError, events::WhitelistRemoved, state::*};
 
 #[derive(Accounts)]
 pub struct RemoveFromWhitelist<'info> {
@@ -8,6 +8,7 @@ pub struct RemoveFromWhitelist<'info> {
 
     #[account(
         has_one = authority,
+        constraint = farm_manager.can(FarmManager::WHITELIST) @ StakingError::MissingPermission,
         seeds = [
             FarmManager::PREFIX,
             farm.key().as_ref(),
@@ -30,5 +31,11 @@ pub fn handler(ctx: Context<RemoveFromWhitelist>) -> Result<()> {
         "Removed {} from whitelist!",
         ctx.accounts.whitelist_proof.whitelisted_address
     );
+
+    emit!(WhitelistRemoved {
+        farm: ctx.accounts.farm.key(),
+        whitelisted_address: ctx.accounts.whitelist_proof.whitelisted_address,
+    });
+
     Ok(())
 }
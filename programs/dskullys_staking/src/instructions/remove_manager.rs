@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::ManagerRemoved, state::*, utils};
+
+#[derive(Accounts)]
+pub struct RemoveManager<'info> {
+    pub farm: Account<'info, Farm>,
+
+    /// CHECK: Not deserialized so managers created before permissions were added can still be
+    /// removed.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            FarmManager::PREFIX,
+            farm.key().as_ref(),
+            manager_authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub farm_manager: UncheckedAccount<'info>,
+
+    /// CHECK: Only used to derive the farm manager address.
+    pub manager_authority: UncheckedAccount<'info>,
+
+    #[account(mut, address = farm.authority)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RemoveManager>) -> Result<()> {
+    utils::close_account(
+        ctx.accounts.farm_manager.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+    )?;
+
+    msg!(
+        "Removed manager {} from farm {}",
+        ctx.accounts.manager_authority.key(),
+        ctx.accounts.farm.key()
+    );
+
+    emit!(ManagerRemoved {
+        farm: ctx.accounts.farm.key(),
+        manager: ctx.accounts.manager_authority.key(),
+    });
+
+    Ok(())
+}
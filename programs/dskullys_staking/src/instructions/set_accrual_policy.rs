@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::AccrualPolicyChanged, state::*};
+
+#[derive(Accounts)]
+pub struct SetAccrualPolicy<'info> {
+    #[account(mut, has_one = authority)]
+    pub farm: Account<'info, Farm>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetAccrualPolicy>, policy: AccrualPolicy) -> Result<()> {
+    ctx.accounts.farm.accrual_policy = policy;
+
+    msg!("Farm accrual policy set to {:?}", policy);
+
+    emit!(AccrualPolicyChanged {
+        farm: ctx.accounts.farm.key(),
+        policy,
+    });
+
+    Ok(())
+}
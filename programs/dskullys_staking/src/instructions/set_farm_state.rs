@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::FarmStateChanged, state::*, utils::now_ts};
+
+#[derive(Accounts)]
+pub struct SetFarmState<'info> {
+    #[account(mut, has_one = authority)]
+    pub farm: Account<'info, Farm>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFarmState>, state: FarmState) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    farm.set_state(state, now_ts()?);
+
+    msg!("Farm state set to {:?}", state);
+
+    emit!(FarmStateChanged {
+        farm: farm.key(),
+        state,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::RewardWindowChanged, state::*, utils::now_ts};
+
+#[derive(Accounts)]
+pub struct SetRewardWindow<'info> {
+    #[account(mut, has_one = authority)]
+    pub farm: Account<'info, Farm>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetRewardWindow>, start_ts: u64, end_ts: u64) -> Result<()> {
+    ctx.accounts.farm.set_reward_window(start_ts, end_ts, now_ts()?)?;
+
+    msg!("Farm rewards set to run from {} to {}", start_ts, end_ts);
+
+    emit!(RewardWindowChanged {
+        farm: ctx.accounts.farm.key(),
+        start_ts,
+        end_ts,
+    });
+
+    Ok(())
+}
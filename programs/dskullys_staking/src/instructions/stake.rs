@@ -4,11 +4,11 @@ use anchor_spl::{
     token::{Mint, Token, TokenAccount},
 };
 
-use crate::{error::*, state::*, utils::*};
+use crate::{error::*, events::Staked, state::*, utils::*};
 
 #[derive(Accounts)]
 pub struct Stake<'info> {
-    #[account(mut)]
+    #[account(mut, constraint = farm.state.can_stake() @ StakingError::StakingPaused)]
     pub farm: Account<'info, Farm>,
 
     #[account(
@@ -24,10 +24,28 @@ pub struct Stake<'info> {
     )]
     pub farmer: Account<'info, Farmer>,
 
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
     pub gem_mint: Account<'info, Mint>,
 
+    #[account(has_one = farm)]
     pub whitelist_proof: Account<'info, WhitelistProof>,
 
+    /// CHECK: May not be initialized when the gem has no buff, read through
+    /// `BuffProof::factor_of`.
+    #[account(
+        seeds = [
+            BuffProof::PREFIX,
+            farm.key().as_ref(),
+            gem_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub buff_proof: UncheckedAccount<'info>,
+
     #[account(
         init_if_needed,
         payer = owner,
@@ -65,71 +83,144 @@ pub struct Stake<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
-impl Stake<'_> {
-    pub fn lock_gem(&self, amount: u64) -> Result<()> {
-        if amount == 0 {
-            return Ok(());
-        }
+/// Moves `amount` gems from the owner to the farmer vault, closing the owner's token account
+/// once it's empty.
+pub fn lock_gems<'info>(
+    gem_owner_ata: AccountInfo<'info>,
+    farmer_vault: AccountInfo<'info>,
+    owner: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount != 0 {
         let cpi_ctx = transfer_spl_ctx(
-            self.gem_owner_ata.to_account_info(),
-            self.farmer_vault.to_account_info(),
-            self.owner.to_account_info(),
-            self.token_program.to_account_info(),
+            gem_owner_ata.clone(),
+            farmer_vault,
+            owner.clone(),
+            token_program.clone(),
+        );
+
+        anchor_spl::token::transfer(cpi_ctx, amount)?;
+    }
+
+    close_ata(gem_owner_ata, owner.clone(), owner, token_program, None)
+}
+
+/// Stakes `amount` gems of `mint` on `receipt`, either as a new stake or by adding fungible
+/// tokens to the running one. Rewards must be settled before, the farmer's rate changes.
+/// The clock doesn't move within a transaction, so every gem of a batch starts at the same time.
+pub fn start_stake<'info>(
+    farm: &mut Account<'info, Farm>,
+    farmer: &mut Account<'info, Farmer>,
+    receipt: &mut StakeReceipt,
+    mint: Pubkey,
+    amount: u64,
+    whitelist_proof: &Account<'info, WhitelistProof>,
+    buff_factor: u64,
+) -> Result<()> {
+    let now = now_ts()?;
+
+    if receipt.farmer != Pubkey::default() {
+        // Receipt account is already initialized.
+        // We're either trying to stake an NFT again, or just trying to stake more fungible tokens.
+        require_keys_eq!(receipt.farmer, farmer.key());
+        require_keys_eq!(receipt.mint, mint);
+        require!(!receipt.is_cooling_down(), StakingError::GemStillStaked);
+    }
+
+    if receipt.farmer != Pubkey::default() && receipt.is_running() {
+        // Only fungible tokens can be added to a running stake.
+        require!(
+            whitelist_proof.ty == WhitelistType::Mint,
+            StakingError::GemStillStaked
         );
 
-        anchor_spl::token::transfer(cpi_ctx, amount)
+        // Stakes older than the farm's counter start being counted once they store their
+        // whitelist.
+        if !receipt.is_migrated() {
+            farm.add_stake()?;
+        }
+
+        farmer.decrease_reward_rate(receipt)?;
+        receipt.add_amount(
+            amount,
+            whitelist_proof.key(),
+            whitelist_proof,
+            buff_factor,
+            now,
+        )?;
+    } else {
+        farm.add_stake()?;
+
+        *receipt = StakeReceipt::new(
+            farmer.key(),
+            mint,
+            now,
+            amount,
+            whitelist_proof.key(),
+            whitelist_proof,
+            buff_factor,
+        )?;
     }
+
+    farmer.increase_reward_rate(receipt)?;
+
+    emit!(Staked {
+        farm: farm.key(),
+        owner: farmer.owner,
+        mint,
+        amount,
+        reward_rate: receipt.reward_rate,
+    });
+
+    Ok(())
 }
 
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, Stake<'info>>,
     amount: u64,
-    has_essence: bool,
+    merkle_proof: Vec<[u8; 32]>,
 ) -> Result<()> {
-    let whitelist_proof = &ctx.accounts.whitelist_proof;
+    let accounts = ctx.accounts;
 
     WhitelistProof::validate(
-        whitelist_proof,
-        &ctx.accounts.gem_mint,
+        &accounts.whitelist_proof,
+        &accounts.gem_mint,
         ctx.program_id,
         ctx.remaining_accounts,
+        &merkle_proof,
     )?;
 
     // Lock the nft to the farmer account.
-    ctx.accounts.lock_gem(amount)?;
-
-    let now_ts = now_ts()?;
-    let reward_rate = amount * ctx.accounts.whitelist_proof.reward_rate;
-    let reward_rate = reward_rate * (has_essence as u64 + 1);
-    let stake_receipt = &mut ctx.accounts.stake_receipt;
+    lock_gems(
+        accounts.gem_owner_ata.to_account_info(),
+        accounts.farmer_vault.to_account_info(),
+        accounts.owner.to_account_info(),
+        accounts.token_program.to_account_info(),
+        amount,
+    )?;
 
-    if stake_receipt.farmer != Pubkey::default() {
-        // Receipt account is already initialized.
-        // We're either trying to stake an NFT again, or just trying to stake more fungible tokens.
-        require_keys_eq!(stake_receipt.farmer, ctx.accounts.farmer.key());
-        require_keys_eq!(stake_receipt.mint, ctx.accounts.gem_mint.key());
-        require!(!stake_receipt.is_running(), StakingError::GemStillStaked);
-    }
+    // Settle what was earned at the current rate before changing it.
+    let mut secondary_reward = SecondaryReward::load(&accounts.secondary_reward)?;
+    accounts.farmer.update_accrued_rewards(
+        &mut accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+    )?;
 
-    **stake_receipt = StakeReceipt::new(
-        ctx.accounts.farmer.key(),
-        ctx.accounts.gem_mint.key(),
-        now_ts,
+    start_stake(
+        &mut accounts.farm,
+        &mut accounts.farmer,
+        &mut accounts.stake_receipt,
+        accounts.gem_mint.key(),
         amount,
-        reward_rate,
-    );
-
-    ctx.accounts
-        .farmer
-        .increase_reward_rate(reward_rate as u64)?;
-
-    close_ata(
-        ctx.accounts.gem_owner_ata.to_account_info(),
-        ctx.accounts.owner.to_account_info(),
-        ctx.accounts.owner.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        None,
+        &accounts.whitelist_proof,
+        BuffProof::factor_of(&accounts.buff_proof)?,
     )?;
 
+    if let Some(secondary_reward) = &mut secondary_reward {
+        accounts.farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
     Ok(())
 }
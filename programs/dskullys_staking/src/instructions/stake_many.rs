@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address, AssociatedToken},
+    token::{Mint, Token},
+};
+
+use crate::{
+    error::*,
+    instructions::{lock_gems, start_stake},
+    state::*,
+    utils::*,
+};
+
+/// Accounts every gem passes in `remaining_accounts`, in this order: mint, owner token
+/// account, farmer vault, stake receipt and buff proof. Creator and collection whitelists
+/// also need the mint's metadata account last.
+pub const STAKE_MANY_GEM_ACCOUNTS: usize = 5;
+
+/// Stakes one NFT of each mint in `remaining_accounts`, all of them from the same whitelist.
+#[derive(Accounts)]
+pub struct StakeMany<'info> {
+    #[account(mut, constraint = farm.state.can_stake() @ StakingError::StakingPaused)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        has_one = owner,
+        seeds = [
+            Farmer::PREFIX,
+            farm.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    pub farmer: Account<'info, Farmer>,
+
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(has_one = farm)]
+    pub whitelist_proof: Account<'info, WhitelistProof>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> StakeMany<'info> {
+    /// Stakes one gem, `gem` holds at least `STAKE_MANY_GEM_ACCOUNTS` accounts.
+    fn stake_gem(
+        &mut self,
+        program_id: &Pubkey,
+        gem: &[AccountInfo<'info>],
+        merkle_proof: &[[u8; 32]],
+    ) -> Result<()> {
+        let gem_mint = &gem[0];
+        let gem_owner_ata = &gem[1];
+        let farmer_vault = &gem[2];
+        let stake_receipt = &gem[3];
+        let buff_proof = &gem[4];
+        let metadata = &gem[STAKE_MANY_GEM_ACCOUNTS..];
+
+        let mint: Account<Mint> = Account::try_from(gem_mint)?;
+
+        WhitelistProof::validate(
+            &self.whitelist_proof,
+            &mint,
+            program_id,
+            metadata,
+            merkle_proof,
+        )?;
+
+        let farm = self.farm.key();
+        let farmer = self.farmer.key();
+
+        let (buff_proof_key, _) = Pubkey::find_program_address(
+            &[BuffProof::PREFIX, farm.as_ref(), mint.key().as_ref()],
+            program_id,
+        );
+        require_keys_eq!(buff_proof.key(), buff_proof_key);
+        let buff_factor = BuffProof::factor_of(buff_proof)?;
+
+        require_keys_eq!(
+            gem_owner_ata.key(),
+            get_associated_token_address(&self.owner.key(), &mint.key())
+        );
+
+        require_keys_eq!(
+            farmer_vault.key(),
+            get_associated_token_address(&farmer, &mint.key())
+        );
+        if farmer_vault.data_is_empty() {
+            associated_token::create(CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: self.owner.to_account_info(),
+                    associated_token: farmer_vault.clone(),
+                    authority: self.farmer.to_account_info(),
+                    mint: gem_mint.clone(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+            ))?;
+        }
+
+        let mut receipt = self.load_receipt(program_id, stake_receipt, &mint.key())?;
+
+        start_stake(
+            &mut self.farm,
+            &mut self.farmer,
+            &mut receipt,
+            mint.key(),
+            1,
+            &self.whitelist_proof,
+            buff_factor,
+        )?;
+        receipt.exit(program_id)?;
+
+        lock_gems(
+            gem_owner_ata.clone(),
+            farmer_vault.clone(),
+            self.owner.to_account_info(),
+            self.token_program.to_account_info(),
+            1,
+        )
+    }
+
+    /// Loads the gem's stake receipt, creating it when the gem was never staked before.
+    fn load_receipt(
+        &self,
+        program_id: &Pubkey,
+        stake_receipt: &AccountInfo<'info>,
+        mint: &Pubkey,
+    ) -> Result<Account<'info, StakeReceipt>> {
+        let farmer = self.farmer.key();
+        let (receipt_key, bump) = Pubkey::find_program_address(
+            &[StakeReceipt::PREFIX, farmer.as_ref(), mint.as_ref()],
+            program_id,
+        );
+        require_keys_eq!(stake_receipt.key(), receipt_key);
+
+        if !stake_receipt.data_is_empty() {
+            return Account::try_from(stake_receipt);
+        }
+
+        initialize_pda(
+            &[StakeReceipt::PREFIX, farmer.as_ref(), mint.as_ref(), &[bump]],
+            8 + StakeReceipt::LEN,
+            program_id,
+            stake_receipt.clone(),
+            self.owner.to_account_info(),
+            self.system_program.to_account_info(),
+        )?;
+
+        Account::try_from_unchecked(stake_receipt)
+    }
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, StakeMany<'info>>,
+    merkle_proofs: Vec<Vec<[u8; 32]>>,
+) -> Result<()> {
+    let gem_accounts = match ctx.accounts.whitelist_proof.ty {
+        WhitelistType::Creator | WhitelistType::Collection => STAKE_MANY_GEM_ACCOUNTS + 1,
+        WhitelistType::MerkleRoot => STAKE_MANY_GEM_ACCOUNTS,
+        // Fungible tokens are staked by amount through `stake`.
+        WhitelistType::Mint => return err!(StakingError::InvalidWhitelistType),
+    };
+
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty() && remaining_accounts.len() % gem_accounts == 0,
+        StakingError::InvalidBatchAccounts
+    );
+
+    let gems = remaining_accounts.len() / gem_accounts;
+    require!(
+        merkle_proofs.is_empty() || merkle_proofs.len() == gems,
+        StakingError::InvalidBatchAccounts
+    );
+
+    // Settle once, every gem below starts earning at the same time.
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+    ctx.accounts.farmer.update_accrued_rewards(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+    )?;
+
+    for (i, gem) in remaining_accounts.chunks(gem_accounts).enumerate() {
+        let merkle_proof = merkle_proofs.get(i).map(Vec::as_slice).unwrap_or(&[]);
+
+        ctx.accounts.stake_gem(ctx.program_id, gem, merkle_proof)?;
+    }
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        ctx.accounts.farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
+    msg!("Staked {} gems", gems);
+
+    Ok(())
+}
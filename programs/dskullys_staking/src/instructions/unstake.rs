@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+use crate::events::Unstaked;
 use crate::utils::{self, close_ata, now_ts};
 
 use crate::state::*;
@@ -24,6 +25,11 @@ pub struct Unstake<'info> {
     )]
     pub farmer: Account<'info, Farmer>,
 
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
     #[account(address = stake_receipt.mint)]
     pub gem_mint: Account<'info, Mint>,
 
@@ -83,18 +89,21 @@ pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
 ) -> Result<()> {
     let now = now_ts()?;
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
 
-    let receipt = &ctx.accounts.stake_receipt;
-    ctx.accounts.release_gems(receipt.amount)?;
-
-    let farm = &mut ctx.accounts.farm;
-    ctx.accounts.farmer.update_accrued_rewards(farm)?;
+    ctx.accounts.farmer.unstake(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+        &mut ctx.accounts.stake_receipt,
+        now,
+    )?;
 
-    ctx.accounts
-        .farmer
-        .decrease_reward_rate(receipt.reward_rate)?;
+    ctx.accounts.release_gems(ctx.accounts.stake_receipt.amount)?;
 
-    ctx.accounts.stake_receipt.end_ts = Some(now);
+    if let Some(secondary_reward) = &mut secondary_reward {
+        ctx.accounts.farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
 
     close_ata(
         ctx.accounts.farmer_vault.to_account_info(),
@@ -104,5 +113,12 @@ pub fn handler<'info>(
         Some(&ctx.accounts.farmer.seeds()),
     )?;
 
+    emit!(Unstaked {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.owner.key(),
+        mint: ctx.accounts.gem_mint.key(),
+        amount: ctx.accounts.stake_receipt.amount,
+    });
+
     Ok(())
 }
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::events::{RewardsClaimed, Unstaked};
+use crate::instructions::claim_secondary_reward;
+use crate::utils::{self, close_ata, now_ts};
+
+use crate::state::*;
+
+/// Same as `unstake`, and pays the farmer's accrued rewards in the same transaction. Farms with a
+/// secondary reward take its vaults as remaining accounts, like `claim_rewards`.
+#[derive(Accounts)]
+pub struct UnstakeAndClaim<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        has_one = owner,
+        seeds = [
+            Farmer::PREFIX,
+            farm.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    pub farmer: Account<'info, Farmer>,
+
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(address = stake_receipt.mint)]
+    pub gem_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = farmer,
+        seeds = [
+            StakeReceipt::PREFIX,
+            farmer.key().as_ref(),
+            gem_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub stake_receipt: Account<'info, StakeReceipt>,
+
+    #[account(
+        mut,
+        associated_token::mint = gem_mint,
+        associated_token::authority = farmer,
+    )]
+    pub farmer_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = gem_mint,
+        associated_token::authority = owner,
+    )]
+    pub gem_owner_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(address = farm.reward.mint)]
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+    )]
+    pub farm_reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+    )]
+    pub farmer_reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> UnstakeAndClaim<'info> {
+    pub fn release_gems(&self, amount: u64) -> Result<()> {
+        let cpi_ctx = utils::transfer_spl_ctx(
+            self.farmer_vault.to_account_info(),
+            self.gem_owner_ata.to_account_info(),
+            self.farmer.to_account_info(),
+            self.token_program.to_account_info(),
+        );
+
+        anchor_spl::token::transfer(
+            cpi_ctx.with_signer(&[&self.farmer.seeds()]),
+            amount,
+        )
+    }
+
+    pub fn transfer_reward(&self, amount: u64) -> Result<()> {
+        let cpi_ctx = utils::transfer_spl_ctx(
+            self.farm_reward_vault.to_account_info(),
+            self.farmer_reward_vault.to_account_info(),
+            self.farm.to_account_info(),
+            self.token_program.to_account_info(),
+        );
+
+        anchor_spl::token::transfer(cpi_ctx.with_signer(&[&self.farm.seeds()]), amount)
+    }
+
+    /// Whether the rewards can be paid along with the gems. Frozen farms still let the gems out,
+    /// and so do farms that can't pay: gems that finished cooling down were unstaked without
+    /// settling, so under the `Fail` policy the claim can still run out of funds. The rewards
+    /// then wait for `claim_rewards`.
+    fn can_claim(&self, secondary_reward: Option<&SecondaryReward>, now: u64) -> bool {
+        if !self.farm.state.can_claim() {
+            return false;
+        }
+
+        let secondary_pending = secondary_reward.map(|secondary_reward| {
+            self.farmer
+                .pending_secondary_rewards(&self.farm, &secondary_reward.reward, now)
+        });
+        let pending = self
+            .farmer
+            .pending_rewards(&self.farm, now)
+            .and(secondary_pending.transpose());
+
+        if let Err(error) = pending {
+            msg!("Rewards were not claimed: {}", error);
+            return false;
+        }
+
+        true
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, UnstakeAndClaim<'info>>) -> Result<()> {
+    let now = now_ts()?;
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+
+    ctx.accounts.farmer.unstake(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+        &mut ctx.accounts.stake_receipt,
+        now,
+    )?;
+
+    ctx.accounts.release_gems(ctx.accounts.stake_receipt.amount)?;
+
+    close_ata(
+        ctx.accounts.farmer_vault.to_account_info(),
+        ctx.accounts.farmer.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        Some(&ctx.accounts.farmer.seeds()),
+    )?;
+
+    emit!(Unstaked {
+        farm: ctx.accounts.farm.key(),
+        owner: ctx.accounts.owner.key(),
+        mint: ctx.accounts.gem_mint.key(),
+        amount: ctx.accounts.stake_receipt.amount,
+    });
+
+    if ctx.accounts.can_claim(secondary_reward.as_deref(), now) {
+        let reward = ctx.accounts.farmer.claim_accrued(
+            &mut ctx.accounts.farm,
+            secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+        )?;
+
+        ctx.accounts.transfer_reward(reward)?;
+
+        msg!("Claimed {} tokens", reward);
+
+        emit!(RewardsClaimed {
+            farm: ctx.accounts.farm.key(),
+            owner: ctx.accounts.owner.key(),
+            mint: ctx.accounts.reward_mint.key(),
+            amount: reward,
+        });
+
+        if let Some(secondary_reward) = &mut secondary_reward {
+            claim_secondary_reward(
+                &ctx.accounts.farm,
+                &mut ctx.accounts.farmer,
+                secondary_reward,
+                ctx.remaining_accounts,
+                ctx.accounts.token_program.to_account_info(),
+            )?;
+        }
+    }
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        ctx.accounts.farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address, AssociatedToken},
+    token::Token,
+};
+
+use crate::error::StakingError;
+use crate::events::Unstaked;
+use crate::utils::{self, close_ata, now_ts};
+
+use crate::state::*;
+
+/// Accounts every gem passes in `remaining_accounts`, in this order: mint, stake receipt,
+/// farmer vault and owner token account.
+pub const UNSTAKE_MANY_GEM_ACCOUNTS: usize = 4;
+
+/// Unstakes every gem in `remaining_accounts`. Like `unstake`, gems with a cooldown must have
+/// begun unstaking first.
+#[derive(Accounts)]
+pub struct UnstakeMany<'info> {
+    #[account(mut)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        has_one = owner,
+        seeds = [
+            Farmer::PREFIX,
+            farm.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    pub farmer: Account<'info, Farmer>,
+
+    /// CHECK: May not be initialized when the farm has no secondary reward, read through
+    /// `SecondaryReward::load`.
+    #[account(mut, seeds = [SecondaryReward::PREFIX, farm.key().as_ref()], bump)]
+    pub secondary_reward: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> UnstakeMany<'info> {
+    /// Unstakes one gem, `gem` holds `UNSTAKE_MANY_GEM_ACCOUNTS` accounts. Rewards must have
+    /// been settled before.
+    fn unstake_gem(
+        &mut self,
+        program_id: &Pubkey,
+        gem: &[AccountInfo<'info>],
+        now: u64,
+    ) -> Result<()> {
+        let gem_mint = &gem[0];
+        let stake_receipt = &gem[1];
+        let farmer_vault = &gem[2];
+        let gem_owner_ata = &gem[3];
+
+        let farmer = self.farmer.key();
+        let owner = self.owner.key();
+
+        let mut receipt: Account<StakeReceipt> = Account::try_from(stake_receipt)?;
+        require_keys_eq!(receipt.farmer, farmer);
+        require_keys_eq!(receipt.mint, gem_mint.key());
+
+        if receipt.is_running() {
+            receipt.require_unlocked(now)?;
+            require!(
+                receipt.unstake_cooldown == 0,
+                StakingError::CooldownIsNotOver
+            );
+
+            self.farmer.decrease_reward_rate(&receipt)?;
+            receipt.end_ts = Some(now);
+        } else {
+            require!(receipt.is_cooling_down(), StakingError::GemNotStaked);
+            receipt.finish_cooldown(now)?;
+        }
+
+        self.farm.remove_stake(&receipt)?;
+
+        receipt.exit(program_id)?;
+
+        require_keys_eq!(
+            farmer_vault.key(),
+            get_associated_token_address(&farmer, &gem_mint.key())
+        );
+
+        require_keys_eq!(
+            gem_owner_ata.key(),
+            get_associated_token_address(&owner, &gem_mint.key())
+        );
+        if gem_owner_ata.data_is_empty() {
+            associated_token::create(CpiContext::new(
+                self.associated_token_program.to_account_info(),
+                associated_token::Create {
+                    payer: self.owner.to_account_info(),
+                    associated_token: gem_owner_ata.clone(),
+                    authority: self.owner.to_account_info(),
+                    mint: gem_mint.clone(),
+                    system_program: self.system_program.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+            ))?;
+        }
+
+        let cpi_ctx = utils::transfer_spl_ctx(
+            farmer_vault.clone(),
+            gem_owner_ata.clone(),
+            self.farmer.to_account_info(),
+            self.token_program.to_account_info(),
+        );
+        anchor_spl::token::transfer(
+            cpi_ctx.with_signer(&[&self.farmer.seeds()]),
+            receipt.amount,
+        )?;
+
+        close_ata(
+            farmer_vault.clone(),
+            self.farmer.to_account_info(),
+            self.owner.to_account_info(),
+            self.token_program.to_account_info(),
+            Some(&self.farmer.seeds()),
+        )?;
+
+        emit!(Unstaked {
+            farm: self.farm.key(),
+            owner,
+            mint: gem_mint.key(),
+            amount: receipt.amount,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, UnstakeMany<'info>>) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !remaining_accounts.is_empty()
+            && remaining_accounts.len() % UNSTAKE_MANY_GEM_ACCOUNTS == 0,
+        StakingError::InvalidBatchAccounts
+    );
+
+    // Settle once, every running gem below stops earning at the same time.
+    let now = now_ts()?;
+    let mut secondary_reward = SecondaryReward::load(&ctx.accounts.secondary_reward)?;
+    ctx.accounts.farmer.settle_accrued_rewards(
+        &mut ctx.accounts.farm,
+        secondary_reward.as_deref_mut().map(|s| &mut s.reward),
+    )?;
+
+    for gem in remaining_accounts.chunks(UNSTAKE_MANY_GEM_ACCOUNTS) {
+        ctx.accounts.unstake_gem(ctx.program_id, gem, now)?;
+    }
+
+    if let Some(secondary_reward) = &mut secondary_reward {
+        ctx.accounts.farmer.sync_secondary_rate(secondary_reward)?;
+        secondary_reward.exit(ctx.program_id)?;
+    }
+
+    msg!(
+        "Unstaked {} gems",
+        remaining_accounts.len() / UNSTAKE_MANY_GEM_ACCOUNTS
+    );
+
+    Ok(())
+}
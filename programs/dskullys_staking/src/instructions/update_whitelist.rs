@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::StakingError, events::WhitelistUpdated, state::*};
+
+#[derive(Accounts)]
+pub struct UpdateWhitelist<'info> {
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        has_one = authority,
+        constraint = farm_manager.can(FarmManager::RATES) @ StakingError::MissingPermission,
+        seeds = [
+            FarmManager::PREFIX,
+            farm.key().as_ref(),
+            authority.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub farm_manager: Account<'info, FarmManager>,
+
+    #[account(mut, has_one = farm)]
+    pub whitelist_proof: Account<'info, WhitelistProof>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateWhitelist>,
+    reward_rate: u64,
+    secondary_reward_rate: u64,
+) -> Result<()> {
+    let whitelist_proof = &mut ctx.accounts.whitelist_proof;
+
+    whitelist_proof.update_reward_rate(reward_rate, secondary_reward_rate)?;
+
+    msg!(
+        "Updated {} reward rates to {} and {} (epoch {})",
+        whitelist_proof.whitelisted_address,
+        reward_rate,
+        secondary_reward_rate,
+        whitelist_proof.rate_epoch
+    );
+
+    emit!(WhitelistUpdated {
+        farm: ctx.accounts.farm.key(),
+        whitelisted_address: whitelist_proof.whitelisted_address,
+        reward_rate,
+        secondary_reward_rate,
+        rate_epoch: whitelist_proof.rate_epoch,
+    });
+
+    Ok(())
+}
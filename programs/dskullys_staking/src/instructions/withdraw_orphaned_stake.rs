@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    error::StakingError,
+    events::Unstaked,
+    state::*,
+    utils::{self, close_ata, now_ts},
+};
+
+/// Returns the gems of a stake whose farm was closed, along with the receipt's rent. Farms
+/// older than the stake counter can be closed with stakes that were never migrated, so their
+/// owners must be able to leave without the farm account. There is nothing left to pay rewards
+/// from, so they are not settled.
+#[derive(Accounts)]
+pub struct WithdrawOrphanedStake<'info> {
+    /// CHECK: Only checked to be closed, its address is all the farmer seeds need.
+    #[account(constraint = farm.data_is_empty() @ StakingError::FarmNotClosed)]
+    pub farm: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = farm,
+        has_one = owner,
+        seeds = [
+            Farmer::PREFIX,
+            farm.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    pub farmer: Account<'info, Farmer>,
+
+    #[account(address = stake_receipt.mint)]
+    pub gem_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = farmer,
+        seeds = [
+            StakeReceipt::PREFIX,
+            farmer.key().as_ref(),
+            gem_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub stake_receipt: Account<'info, StakeReceipt>,
+
+    #[account(
+        mut,
+        associated_token::mint = gem_mint,
+        associated_token::authority = farmer,
+    )]
+    pub farmer_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = gem_mint,
+        associated_token::authority = owner,
+    )]
+    pub gem_owner_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<WithdrawOrphanedStake>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let amount = accounts.stake_receipt.amount;
+
+    accounts
+        .farmer
+        .withdraw_orphaned(&mut accounts.stake_receipt, now_ts()?)?;
+
+    let farmer_seeds = accounts.farmer.seeds();
+    let cpi_ctx = utils::transfer_spl_ctx(
+        accounts.farmer_vault.to_account_info(),
+        accounts.gem_owner_ata.to_account_info(),
+        accounts.farmer.to_account_info(),
+        accounts.token_program.to_account_info(),
+    );
+    anchor_spl::token::transfer(cpi_ctx.with_signer(&[&farmer_seeds]), amount)?;
+
+    close_ata(
+        accounts.farmer_vault.to_account_info(),
+        accounts.farmer.to_account_info(),
+        accounts.owner.to_account_info(),
+        accounts.token_program.to_account_info(),
+        Some(&farmer_seeds),
+    )?;
+
+    emit!(Unstaked {
+        farm: accounts.farm.key(),
+        owner: accounts.owner.key(),
+        mint: accounts.gem_mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{events::RewardWithdrawn, state::*, utils};
+
+#[derive(Accounts)]
+pub struct WithdrawSecondaryReward<'info> {
+    #[account(has_one = authority)]
+    pub farm: Account<'info, Farm>,
+
+    #[account(mut, has_one = farm)]
+    pub secondary_reward: Account<'info, SecondaryReward>,
+
+    #[account(address = secondary_reward.reward.mint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = farm,
+    )]
+    pub farm_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+    )]
+    pub authority_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+impl<'info> WithdrawSecondaryReward<'info> {
+    pub fn transfer_reward(&self, amount: u64) -> Result<()> {
+        let cpi_ctx = utils::transfer_spl_ctx(
+            self.farm_vault.to_account_info(),
+            self.authority_ata.to_account_info(),
+            self.farm.to_account_info(),
+            self.token_program.to_account_info(),
+        );
+
+        anchor_spl::token::transfer(cpi_ctx.with_signer(&[&self.farm.seeds()]), amount)
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawSecondaryReward>, amount: u64) -> Result<()> {
+    // Only the unreserved funds can be withdrawn, secondary rewards already accrued by the
+    // farmers are kept in the vault.
+    ctx.accounts.secondary_reward.reward.try_withdraw(amount)?;
+
+    ctx.accounts.transfer_reward(amount)?;
+
+    msg!("Withdrew {} secondary reward tokens", amount);
+
+    emit!(RewardWithdrawn {
+        farm: ctx.accounts.farm.key(),
+        authority: ctx.accounts.authority.key(),
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
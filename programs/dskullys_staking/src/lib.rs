@@ -1,19 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
-};
 
 declare_id!("DkMt4VqQvgeivRjqpL3bQrwfRgKX4n1xRGCbF2acfSpC");
 
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod merkle;
 pub mod state;
 pub(crate) mod utils;
 
 use instructions::*;
 use state::*;
-use utils::close_ata;
 
 #[program]
 pub mod dskullys_staking {
@@ -23,22 +20,68 @@ pub mod dskullys_staking {
         instructions::create_farm::handler(ctx)
     }
 
-    pub fn add_manager(ctx: Context<AddManager>) -> Result<()> {
-        instructions::add_manager::handler(ctx)
+    pub fn add_buff(ctx: Context<AddBuff>, factor: u64) -> Result<()> {
+        instructions::add_buff::handler(ctx, factor)
+    }
+
+    pub fn remove_buff(ctx: Context<RemoveBuff>) -> Result<()> {
+        instructions::remove_buff::handler(ctx)
+    }
+
+    pub fn add_manager(ctx: Context<AddManager>, permissions: u8) -> Result<()> {
+        instructions::add_manager::handler(ctx, permissions)
+    }
+
+    pub fn remove_manager(ctx: Context<RemoveManager>) -> Result<()> {
+        instructions::remove_manager::handler(ctx)
+    }
+
+    pub fn migrate_farm_manager(ctx: Context<MigrateFarmManager>) -> Result<()> {
+        instructions::migrate_farm_manager::handler(ctx)
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>) -> Result<()> {
+        instructions::propose_authority::handler(ctx)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        instructions::cancel_authority_transfer::handler(ctx)
     }
 
     pub fn add_to_whitelist(
         ctx: Context<AddToWhitelist>,
         reward_rate: u64,
         whitelist_type: WhitelistType,
+        min_stake_duration: u64,
+        unstake_cooldown: u32,
+        secondary_reward_rate: u64,
     ) -> Result<()> {
         instructions::add_to_whitelist::handler(
             ctx,
             reward_rate,
             whitelist_type,
+            min_stake_duration,
+            unstake_cooldown,
+            secondary_reward_rate,
         )
     }
 
+    pub fn update_whitelist(
+        ctx: Context<UpdateWhitelist>,
+        reward_rate: u64,
+        secondary_reward_rate: u64,
+    ) -> Result<()> {
+        instructions::update_whitelist::handler(ctx, reward_rate, secondary_reward_rate)
+    }
+
+    pub fn migrate_whitelist_proof(ctx: Context<MigrateWhitelistProof>) -> Result<()> {
+        instructions::migrate_whitelist_proof::handler(ctx)
+    }
+
     pub fn remove_from_whitelist(
         ctx: Context<RemoveFromWhitelist>,
     ) -> Result<()> {
@@ -49,6 +92,66 @@ pub mod dskullys_staking {
         instructions::fund_reward::handler(ctx, amount)
     }
 
+    pub fn add_secondary_reward(ctx: Context<AddSecondaryReward>) -> Result<()> {
+        instructions::add_secondary_reward::handler(ctx)
+    }
+
+    pub fn fund_secondary_reward(ctx: Context<FundSecondaryReward>, amount: u64) -> Result<()> {
+        instructions::fund_secondary_reward::handler(ctx, amount)
+    }
+
+    pub fn withdraw_secondary_reward(
+        ctx: Context<WithdrawSecondaryReward>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_secondary_reward::handler(ctx, amount)
+    }
+
+    pub fn close_secondary_reward(ctx: Context<CloseSecondaryReward>) -> Result<()> {
+        instructions::close_secondary_reward::handler(ctx)
+    }
+
+    pub fn withdraw_reward(
+        ctx: Context<WithdrawReward>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_reward::handler(ctx, amount)
+    }
+
+    pub fn set_accrual_policy(
+        ctx: Context<SetAccrualPolicy>,
+        policy: AccrualPolicy,
+    ) -> Result<()> {
+        instructions::set_accrual_policy::handler(ctx, policy)
+    }
+
+    pub fn set_farm_state(ctx: Context<SetFarmState>, state: FarmState) -> Result<()> {
+        instructions::set_farm_state::handler(ctx, state)
+    }
+
+    pub fn finish_stake_migration(ctx: Context<FinishStakeMigration>) -> Result<()> {
+        instructions::finish_stake_migration::handler(ctx)
+    }
+
+    pub fn close_farm(ctx: Context<CloseFarm>) -> Result<()> {
+        instructions::close_farm::handler(ctx)
+    }
+
+    pub fn set_reward_window(
+        ctx: Context<SetRewardWindow>,
+        start_ts: u64,
+        end_ts: u64,
+    ) -> Result<()> {
+        instructions::set_reward_window::handler(ctx, start_ts, end_ts)
+    }
+
+    pub fn extend_reward_window(
+        ctx: Context<ExtendRewardWindow>,
+        end_ts: u64,
+    ) -> Result<()> {
+        instructions::extend_reward_window::handler(ctx, end_ts)
+    }
+
     pub fn initialize_farmer(ctx: Context<InitializeFarmer>) -> Result<()> {
         instructions::initialize_farmer::handler(ctx)
     }
@@ -56,9 +159,31 @@ pub mod dskullys_staking {
     pub fn stake<'info>(
         ctx: Context<'_, '_, '_, 'info, Stake<'info>>,
         amount: u64,
-        has_essence: bool,
+        merkle_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::stake::handler(ctx, amount, merkle_proof)
+    }
+
+    pub fn stake_many<'info>(
+        ctx: Context<'_, '_, '_, 'info, StakeMany<'info>>,
+        merkle_proofs: Vec<Vec<[u8; 32]>>,
+    ) -> Result<()> {
+        instructions::stake_many::handler(ctx, merkle_proofs)
+    }
+
+    pub fn refresh_stake(ctx: Context<RefreshStake>) -> Result<()> {
+        instructions::refresh_stake::handler(ctx)
+    }
+
+    pub fn migrate_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateStake<'info>>,
+        merkle_proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        instructions::stake::handler(ctx, amount, has_essence)
+        instructions::migrate_stake::handler(ctx, merkle_proof)
+    }
+
+    pub fn begin_unstake(ctx: Context<BeginUnstake>) -> Result<()> {
+        instructions::begin_unstake::handler(ctx)
     }
 
     pub fn unstake<'info>(
@@ -67,90 +192,44 @@ pub mod dskullys_staking {
         instructions::unstake::handler(ctx)
     }
 
-    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    pub fn unstake_and_claim<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnstakeAndClaim<'info>>,
+    ) -> Result<()> {
+        instructions::unstake_and_claim::handler(ctx)
+    }
+
+    pub fn unstake_many<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnstakeMany<'info>>,
+    ) -> Result<()> {
+        instructions::unstake_many::handler(ctx)
+    }
+
+    pub fn partial_unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, PartialUnstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::partial_unstake::handler(ctx, amount)
+    }
+
+    pub fn claim_rewards<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRewards<'info>>,
+    ) -> Result<()> {
         instructions::claim_rewards::handler(ctx)
     }
 
+    pub fn close_stake_receipt(ctx: Context<CloseStakeReceipt>) -> Result<()> {
+        instructions::close_stake_receipt::handler(ctx)
+    }
+
+    pub fn close_farmer(ctx: Context<CloseFarmer>) -> Result<()> {
+        instructions::close_farmer::handler(ctx)
+    }
+
     pub fn force_unstake(ctx: Context<ForceUnstake>) -> Result<()> {
-        let cpi_ctx = utils::transfer_spl_ctx(
-            ctx.accounts.farmer_vault.to_account_info(),
-            ctx.accounts.gem_owner_ata.to_account_info(),
-            ctx.accounts.farmer.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-        );
-
-        anchor_spl::token::transfer(
-            cpi_ctx.with_signer(&[&ctx.accounts.farmer.seeds()]),
-            1,
-        )?;
-
-        close_ata(
-            ctx.accounts.farmer_vault.to_account_info(),
-            ctx.accounts.farmer.to_account_info(),
-            ctx.accounts.owner.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            Some(&ctx.accounts.farmer.seeds()),
-        )?;
-
-        Ok(())
+        instructions::force_unstake::handler(ctx)
     }
-}
 
-#[derive(Accounts)]
-pub struct ForceUnstake<'info> {
-    #[account(mut)]
-    pub farm: Account<'info, Farm>,
-
-    #[account(
-        mut,
-        has_one = farm,
-        has_one = owner,
-        seeds = [
-            Farmer::PREFIX,
-            farm.key().as_ref(),
-            owner.key().as_ref()
-        ],
-        bump,
-    )]
-    pub farmer: Account<'info, Farmer>,
-
-    #[account(address = stake_receipt.mint)]
-    pub gem_mint: Account<'info, Mint>,
-
-    #[account(
-        mut,
-        has_one = farmer,
-        seeds = [
-            StakeReceipt::PREFIX,
-            farmer.key().as_ref(),
-            gem_mint.key().as_ref(),
-        ],
-        bump,
-    )]
-    pub stake_receipt: Account<'info, StakeReceipt>,
-
-    #[account(
-        mut,
-        associated_token::mint = gem_mint,
-        associated_token::authority = farmer,
-    )]
-    pub farmer_vault: Box<Account<'info, TokenAccount>>,
-
-    #[account(
-        init_if_needed,
-        payer = authority,
-        associated_token::mint = gem_mint,
-        associated_token::authority = owner,
-    )]
-    pub gem_owner_ata: Box<Account<'info, TokenAccount>>,
-
-    pub owner: SystemAccount<'info>,
-
-    #[account(mut)]
-    pub authority: Signer<'info>,
-
-    pub rent: Sysvar<'info, Rent>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub fn withdraw_orphaned_stake(ctx: Context<WithdrawOrphanedStake>) -> Result<()> {
+        instructions::withdraw_orphaned_stake::handler(ctx)
+    }
 }
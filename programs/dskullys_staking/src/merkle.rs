@@ -0,0 +1,115 @@
+//! Merkle tree helpers used by `WhitelistType::MerkleRoot` whitelists.
+//!
+//! Leaves and nodes are hashed with different prefixes so a node can never be passed off as a
+//! leaf, and pairs are sorted before hashing so proofs don't need to carry sibling positions.
+use anchor_lang::{prelude::Pubkey, solana_program::keccak};
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+pub fn leaf(mint: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[&[LEAF_PREFIX], mint.as_ref()]).to_bytes()
+}
+
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[&[NODE_PREFIX], left, right]).to_bytes()
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling))
+        == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Builds the tree bottom up, carrying odd nodes to the next level unhashed, and returns the
+    // root together with the proof for every leaf.
+    fn tree(leaves: &[[u8; 32]]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+        let mut proofs = vec![Vec::new(); leaves.len()];
+        let mut positions: Vec<usize> = (0..leaves.len()).collect();
+        let mut level = leaves.to_vec();
+
+        while level.len() > 1 {
+            for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+                let sibling = *position ^ 1;
+                if sibling < level.len() {
+                    proof.push(level[sibling]);
+                }
+                *position /= 2;
+            }
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => hash_pair(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+
+        (level[0], proofs)
+    }
+
+    fn mints(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+
+    #[test]
+    fn valid_proofs_verify() {
+        let mints = mints(5);
+        let leaves: Vec<_> = mints.iter().map(leaf).collect();
+        let (root, proofs) = tree(&leaves);
+
+        for (mint, proof) in mints.iter().zip(&proofs) {
+            assert!(verify(proof, &root, leaf(mint)));
+        }
+    }
+
+    #[test]
+    fn wrong_sibling_is_rejected() {
+        let mints = mints(4);
+        let leaves: Vec<_> = mints.iter().map(leaf).collect();
+        let (root, proofs) = tree(&leaves);
+
+        let mut proof = proofs[0].clone();
+        proof[0] = leaf(&Pubkey::new_unique());
+        assert!(!verify(&proof, &root, leaf(&mints[0])));
+
+        // A proof for one mint doesn't prove another.
+        assert!(!verify(&proofs[0], &root, leaf(&mints[2])));
+        assert!(!verify(&proofs[0], &root, leaf(&Pubkey::new_unique())));
+    }
+
+    #[test]
+    fn single_leaf_tree_has_an_empty_proof() {
+        let mint = Pubkey::new_unique();
+        let (root, proofs) = tree(&[leaf(&mint)]);
+
+        assert_eq!(root, leaf(&mint));
+        assert!(proofs[0].is_empty());
+        assert!(verify(&[], &root, leaf(&mint)));
+        assert!(!verify(&[], &root, leaf(&Pubkey::new_unique())));
+    }
+
+    #[test]
+    fn inner_nodes_are_not_leaves() {
+        let mints = mints(4);
+        let leaves: Vec<_> = mints.iter().map(leaf).collect();
+        let (root, _) = tree(&leaves);
+
+        // Passing off the first inner node as a leaf, with the other inner node as its proof,
+        // would verify if leaves and nodes were hashed the same way.
+        let left = hash_pair(&leaves[0], &leaves[1]);
+        let right = hash_pair(&leaves[2], &leaves[3]);
+        assert_eq!(hash_pair(&left, &right), root);
+
+        let forged = Pubkey::new_from_array(left);
+        assert_ne!(leaf(&forged), left);
+        assert!(!verify(&[right], &root, leaf(&forged)));
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct AuthorityTransfer {
+    pub farm: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+impl AuthorityTransfer {
+    pub const LEN: usize = 32 + 32;
+    pub const PREFIX: &'static [u8] = b"authority_transfer";
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct BuffProof {
+    pub farm: Pubkey,
+    pub mint: Pubkey,
+    // Reward rate bonus, in percent (100 doubles the rate).
+    pub factor: u64,
+}
+
+impl BuffProof {
+    pub const LEN: usize = 32 + 32 + 8;
+    pub const PREFIX: &'static [u8] = b"buff_proof";
+
+    /// Reads the buff factor from a buff proof account that may not have been initialized, in
+    /// which case the gem has no buff.
+    pub fn factor_of(account: &AccountInfo) -> Result<u64> {
+        if account.data_is_empty() {
+            return Ok(0);
+        }
+
+        let proof: Account<Self> = Account::try_from(account)?;
+
+        Ok(proof.factor)
+    }
+}
@@ -2,7 +2,15 @@ use anchor_lang::prelude::*;
 
 use crate::error::StakingError;
 
-#[derive(Copy, Clone, Debug, AnchorSerialize, AnchorDeserialize)]
+use super::StakeReceipt;
+
+/// Bookkeeping of the farm's reward vault.
+///
+/// Every token in the vault is either `available`, meaning it can still be reserved for the
+/// farmers or withdrawn by the farm authority, or `reserved`, meaning it was already accrued by
+/// some farmer and is waiting to be claimed. Tokens only move between the two buckets or in and
+/// out of the vault, so `available + reserved` always matches the vault balance.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, AnchorSerialize, AnchorDeserialize)]
 pub struct Reward {
     pub mint: Pubkey,
     pub reserved: u64,
@@ -20,6 +28,14 @@ impl Reward {
         }
     }
 
+    /// Amount of tokens that should be held by the reward vault.
+    pub fn total(&self) -> Result<u64> {
+        self.available
+            .checked_add(self.reserved)
+            .ok_or_else(|| StakingError::ArithmeticError.into())
+    }
+
+    /// Tokens deposited into the vault.
     pub fn try_fund(&mut self, amount: u64) -> Result<()> {
         self.available = self
             .available
@@ -28,50 +44,109 @@ impl Reward {
         Ok(())
     }
 
+    /// Tokens accrued by a farmer, moved from `available` to `reserved`.
     pub fn try_reserve(&mut self, amount: u64) -> Result<()> {
-        let increment = self
+        let available = self
+            .available
+            .checked_sub(amount)
+            .ok_or(StakingError::CouldNotReserveReward)?;
+
+        let reserved = self
             .reserved
             .checked_add(amount)
             .ok_or(StakingError::ArithmeticError)?;
 
-        require_gte!(
-            self.available,
-            increment,
-            StakingError::CouldNotReserveReward
-        );
+        self.available = available;
+        self.reserved = reserved;
 
-        self.reserved = increment;
+        Ok(())
+    }
 
+    /// Unreserved tokens taken out of the vault.
+    pub fn try_withdraw(&mut self, amount: u64) -> Result<()> {
         self.available = self
             .available
-            .checked_sub(increment)
-            .ok_or(StakingError::ArithmeticError)?;
+            .checked_sub(amount)
+            .ok_or(StakingError::CouldNotWithdrawReward)?;
 
         Ok(())
     }
 
+    /// Reserved tokens claimed by a farmer, taken out of the vault.
     pub fn try_release(&mut self, amount: u64) -> Result<()> {
-        let decrement = self
+        self.reserved = self
             .reserved
             .checked_sub(amount)
             .ok_or(StakingError::CouldNotReleaseReward)?;
 
-        self.reserved = decrement;
-
         Ok(())
     }
 }
 
+/// What to do when a farmer accrues more rewards than the farm has available.
+#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum AccrualPolicy {
+    // Fail the transaction (unstaking always falls back to `Partial`).
+    Fail,
+    // Accrue whatever is still available.
+    Partial,
+    // Accrue nothing until the farm is funded again.
+    Skip,
+}
+
+/// Lets the farm authority stop parts of the farm, e.g. while a misconfigured whitelist is
+/// fixed. Unstaking is always possible.
+#[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum FarmState {
+    Active,
+    // No new stakes, staked gems keep earning.
+    StakingPaused,
+    // Staked gems stop earning, but new stakes and claims still go through.
+    RewardsPaused,
+    // Nothing but unstaking.
+    Frozen,
+}
+
+impl FarmState {
+    pub fn can_stake(&self) -> bool {
+        matches!(self, FarmState::Active | FarmState::RewardsPaused)
+    }
+
+    pub fn emits_rewards(&self) -> bool {
+        matches!(self, FarmState::Active | FarmState::StakingPaused)
+    }
+
+    pub fn can_claim(&self) -> bool {
+        *self != FarmState::Frozen
+    }
+}
+
 #[account]
 pub struct Farm {
     pub authority: Pubkey,
     pub reward: Reward,
     pub bump: [u8; 1],
-    _reserved: [u8; 64],
+    pub accrual_policy: AccrualPolicy,
+    // Rewards are only emitted between these timestamps. An end of zero means no end.
+    pub reward_start_ts: u64,
+    pub reward_end_ts: u64,
+    // Authority the farm address was derived from, `authority` can be transferred.
+    seed_authority: Pubkey,
+    pub state: FarmState,
+    // Seconds of the reward window rewards were paused for while they are emitted, and that
+    // minus the start of the current pause otherwise. See `paused_seconds`.
+    paused_time: u64,
+    // Stake receipts holding gems, including the ones cooling down. Only receipts that store
+    // their whitelist are counted, see `remove_stake`.
+    pub active_stakes: u32,
+    // Whether `active_stakes` counts every stake. Farms created before the counter only set it
+    // once their older stakes were migrated, through `finish_stake_migration`.
+    pub stakes_counted: bool,
+    _reserved: [u8; 1],
 }
 
 impl Farm {
-    pub const LEN: usize = 32 + Reward::LEN + 1 + 64;
+    pub const LEN: usize = 32 + Reward::LEN + 1 + 1 + 8 + 8 + 32 + 1 + 8 + 4 + 1 + 1;
     pub const PREFIX: &'static [u8] = b"farm";
 
     pub fn new(authority: Pubkey, reward: Reward, bump: u8) -> Self {
@@ -79,16 +154,424 @@ impl Farm {
             authority,
             reward,
             bump: [bump],
-            _reserved: [0; 64],
+            accrual_policy: AccrualPolicy::Fail,
+            reward_start_ts: 0,
+            reward_end_ts: 0,
+            seed_authority: authority,
+            state: FarmState::Active,
+            paused_time: 0,
+            active_stakes: 0,
+            stakes_counted: true,
+            _reserved: [0; 1],
+        }
+    }
+
+    pub fn seed_authority(&self) -> &Pubkey {
+        // Farms created before authorities could be transferred never had it set.
+        if self.seed_authority == Pubkey::default() {
+            &self.authority
+        } else {
+            &self.seed_authority
+        }
+    }
+
+    pub fn transfer_authority(&mut self, new_authority: Pubkey) {
+        self.seed_authority = *self.seed_authority();
+        self.authority = new_authority;
+    }
+
+    pub fn set_state(&mut self, state: FarmState, now: u64) {
+        let window_ts = self.reward_duration(0, now);
+
+        match (self.state.emits_rewards(), state.emits_rewards()) {
+            (true, false) => self.paused_time = self.paused_time.wrapping_sub(window_ts),
+            (false, true) => self.paused_time = self.paused_time.wrapping_add(window_ts),
+            _ => {}
+        }
+
+        self.state = state;
+    }
+
+    /// Seconds of the reward window the rewards were paused for since the farm was created.
+    /// Farmers keep the value of their last update, so the time paused since then isn't
+    /// rewarded. Pauses are clipped to the window like the time farmers earn for, so a pause
+    /// outside of it doesn't take anything from them.
+    pub fn paused_seconds(&self, now: u64) -> u64 {
+        if self.state.emits_rewards() {
+            self.paused_time
+        } else {
+            self.paused_time.wrapping_add(self.reward_duration(0, now))
         }
     }
 
+    pub fn add_stake(&mut self) -> Result<()> {
+        self.active_stakes = self
+            .active_stakes
+            .checked_add(1)
+            .ok_or(StakingError::ArithmeticError)?;
+        Ok(())
+    }
+
+    /// Stops counting the stake of `receipt`. Stakes made before the counter existed don't
+    /// store their whitelist and were never added, until `migrate_stake` counts them.
+    pub fn remove_stake(&mut self, receipt: &StakeReceipt) -> Result<()> {
+        if receipt.is_migrated() {
+            self.active_stakes = self
+                .active_stakes
+                .checked_sub(1)
+                .ok_or(StakingError::ArithmeticError)?;
+        }
+        Ok(())
+    }
+
+    /// Farmers are only settled on their next interaction, so the window also applies to the
+    /// time they haven't been settled for yet. To not take back or hand out rewards for that
+    /// time, a window that has started keeps its start and the end can't move into the past.
+    pub fn set_reward_window(&mut self, start_ts: u64, end_ts: u64, now: u64) -> Result<()> {
+        require!(
+            end_ts == 0 || (end_ts > start_ts && end_ts >= now),
+            StakingError::InvalidRewardWindow
+        );
+
+        if start_ts != self.reward_start_ts {
+            require!(
+                self.reward_start_ts > now && start_ts >= now,
+                StakingError::InvalidRewardWindow
+            );
+        }
+
+        self.reward_start_ts = start_ts;
+        self.reward_end_ts = end_ts;
+
+        Ok(())
+    }
+
+    /// Seconds between `from` and `to` that fall inside the reward window.
+    pub fn reward_duration(&self, from: u64, to: u64) -> u64 {
+        let start = from.max(self.reward_start_ts);
+        let end = match self.reward_end_ts {
+            0 => to,
+            end_ts => to.min(end_ts),
+        };
+
+        end.saturating_sub(start)
+    }
+
     pub fn seeds(&self) -> [&[u8]; 4] {
         [
             Self::PREFIX,
-            self.authority.as_ref(),
+            self.seed_authority().as_ref(),
             self.reward.mint.as_ref(),
             &self.bump,
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{WhitelistProof, WhitelistType};
+
+    #[derive(Debug, Clone, Copy)]
+    enum Op {
+        Fund(u64),
+        Reserve(u64),
+        Release(u64),
+        Withdraw(u64),
+    }
+
+    // Small xorshift generator, so the sequences are random but reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn op(&mut self, max_amount: u64) -> Op {
+            let amount = self.next() % max_amount;
+            match self.next() % 4 {
+                0 => Op::Fund(amount),
+                1 => Op::Reserve(amount),
+                2 => Op::Release(amount),
+                _ => Op::Withdraw(amount),
+            }
+        }
+    }
+
+    // Applies `op` to both the reward and a model of the vault balance. The vault only changes
+    // when the reward accepts the operation.
+    fn apply(reward: &mut Reward, vault: &mut u64, op: Op) -> Result<()> {
+        match op {
+            Op::Fund(amount) => {
+                reward.try_fund(amount)?;
+                *vault += amount;
+            }
+            Op::Reserve(amount) => reward.try_reserve(amount)?,
+            Op::Release(amount) => {
+                reward.try_release(amount)?;
+                *vault -= amount;
+            }
+            Op::Withdraw(amount) => {
+                reward.try_withdraw(amount)?;
+                *vault -= amount;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn reserve_only_moves_the_increment() {
+        let mut reward = Reward::new(Pubkey::new_unique());
+
+        reward.try_fund(1_000).unwrap();
+        reward.try_reserve(100).unwrap();
+        reward.try_reserve(100).unwrap();
+        reward.try_reserve(100).unwrap();
+
+        assert_eq!(reward.reserved, 300);
+        assert_eq!(reward.available, 700);
+        assert_eq!(reward.total().unwrap(), 1_000);
+    }
+
+    #[test]
+    fn cannot_reserve_more_than_available() {
+        let mut reward = Reward::new(Pubkey::new_unique());
+
+        reward.try_fund(100).unwrap();
+        reward.try_reserve(60).unwrap();
+
+        assert!(reward.try_reserve(41).is_err());
+        reward.try_reserve(40).unwrap();
+        assert_eq!(reward.available, 0);
+    }
+
+    #[test]
+    fn withdraw_never_touches_reserved() {
+        let mut reward = Reward::new(Pubkey::new_unique());
+
+        reward.try_fund(100).unwrap();
+        reward.try_reserve(30).unwrap();
+
+        assert!(reward.try_withdraw(71).is_err());
+        reward.try_withdraw(70).unwrap();
+        assert_eq!(reward.reserved, 30);
+    }
+
+    #[test]
+    fn paused_seconds_only_count_pauses() {
+        let mut farm = Farm::new(Pubkey::new_unique(), Reward::new(Pubkey::new_unique()), 255);
+
+        farm.set_state(FarmState::StakingPaused, 100);
+        assert_eq!(farm.paused_seconds(150), 0);
+
+        farm.set_state(FarmState::RewardsPaused, 200);
+        assert_eq!(farm.paused_seconds(250), 50);
+
+        // Freezing a farm whose rewards are already paused keeps the same pause going.
+        farm.set_state(FarmState::Frozen, 300);
+        assert_eq!(farm.paused_seconds(350), 150);
+
+        farm.set_state(FarmState::Active, 400);
+        assert_eq!(farm.paused_seconds(1_000), 200);
+
+        farm.set_state(FarmState::RewardsPaused, 1_000);
+        farm.set_state(FarmState::Active, 1_010);
+        assert_eq!(farm.paused_seconds(2_000), 210);
+    }
+
+    #[test]
+    fn paused_seconds_only_count_the_reward_window() {
+        let mut farm = Farm::new(Pubkey::new_unique(), Reward::new(Pubkey::new_unique()), 255);
+        farm.reward_start_ts = 100;
+        farm.reward_end_ts = 500;
+
+        farm.set_state(FarmState::RewardsPaused, 50);
+        assert_eq!(farm.paused_seconds(80), 0);
+        assert_eq!(farm.paused_seconds(150), 50);
+
+        farm.set_state(FarmState::Active, 200);
+        farm.set_state(FarmState::RewardsPaused, 450);
+        assert_eq!(farm.paused_seconds(1_000), 150);
+
+        farm.set_state(FarmState::Active, 1_000);
+        farm.set_state(FarmState::RewardsPaused, 2_000);
+        assert_eq!(farm.paused_seconds(3_000), 150);
+    }
+
+    fn receipt(whitelist: Pubkey) -> StakeReceipt {
+        let whitelist_proof = WhitelistProof::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            1,
+            0,
+            WhitelistType::Mint,
+            0,
+            0,
+        );
+
+        StakeReceipt::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            1,
+            whitelist,
+            &whitelist_proof,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn stakes_made_before_the_counter_dont_underflow() {
+        let mut farm = Farm::new(Pubkey::new_unique(), Reward::new(Pubkey::new_unique()), 255);
+        let legacy = receipt(Pubkey::default());
+        let stake = receipt(Pubkey::new_unique());
+
+        farm.add_stake().unwrap();
+        farm.remove_stake(&legacy).unwrap();
+        assert_eq!(farm.active_stakes, 1);
+        farm.remove_stake(&stake).unwrap();
+        assert_eq!(farm.active_stakes, 0);
+        assert!(farm.remove_stake(&stake).is_err());
+
+        farm.active_stakes = u32::MAX;
+        assert!(farm.add_stake().is_err());
+    }
+
+    #[test]
+    fn older_stakes_dont_hide_newer_ones() {
+        let mut farm = Farm::new(Pubkey::new_unique(), Reward::new(Pubkey::new_unique()), 255);
+        farm.stakes_counted = false;
+        let mut legacy = receipt(Pubkey::default());
+        let stake = receipt(Pubkey::new_unique());
+
+        // A new stake comes in, then a stake older than the counter leaves.
+        farm.add_stake().unwrap();
+        farm.remove_stake(&legacy).unwrap();
+        assert_eq!(farm.active_stakes, 1);
+
+        // Migrated stakes are counted like the new ones.
+        legacy.migrate(Pubkey::new_unique()).unwrap();
+        farm.add_stake().unwrap();
+        farm.remove_stake(&legacy).unwrap();
+        farm.remove_stake(&stake).unwrap();
+        assert_eq!(farm.active_stakes, 0);
+    }
+
+    fn farm() -> Farm {
+        Farm::new(Pubkey::new_unique(), Reward::new(Pubkey::new_unique()), 255)
+    }
+
+    #[test]
+    fn reward_duration_only_counts_the_window() {
+        let mut farm = farm();
+        assert_eq!(farm.reward_duration(100, 200), 100);
+
+        farm.reward_start_ts = 150;
+        assert_eq!(farm.reward_duration(100, 200), 50);
+        assert_eq!(farm.reward_duration(100, 150), 0);
+        // Not started yet.
+        assert_eq!(farm.reward_duration(100, 120), 0);
+
+        farm.reward_end_ts = 180;
+        assert_eq!(farm.reward_duration(100, 200), 30);
+        assert_eq!(farm.reward_duration(160, 170), 10);
+        // Already over.
+        assert_eq!(farm.reward_duration(190, 300), 0);
+
+        // No end.
+        farm.reward_end_ts = 0;
+        assert_eq!(farm.reward_duration(160, 1_000), 840);
+    }
+
+    #[test]
+    fn reward_window_cannot_change_the_past() {
+        let mut farm = farm();
+        let now = 1_000;
+
+        // The end must come after both the start and now.
+        assert!(farm.set_reward_window(0, 900, now).is_err());
+        assert!(farm.set_reward_window(2_000, 1_500, now).is_err());
+
+        // The default window started when the farm was created.
+        assert!(farm.set_reward_window(1_000, 0, now).is_err());
+        farm.set_reward_window(0, 1_500, now).unwrap();
+        assert_eq!(farm.reward_end_ts, 1_500);
+
+        // A window that hasn't started can be moved, but not back into the past.
+        farm.reward_start_ts = 1_200;
+        assert!(farm.set_reward_window(900, 1_500, now).is_err());
+        farm.set_reward_window(1_100, 1_500, now).unwrap();
+        assert_eq!(farm.reward_start_ts, 1_100);
+
+        // Once it starts, only the end can change.
+        let now = 1_300;
+        assert!(farm.set_reward_window(1_400, 2_000, now).is_err());
+        farm.set_reward_window(1_100, 2_000, now).unwrap();
+        farm.set_reward_window(1_100, 0, now).unwrap();
+        assert_eq!(farm.reward_end_ts, 0);
+    }
+
+    #[test]
+    fn random_sequences_keep_the_vault_invariant() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..500 {
+            let mut reward = Reward::new(Pubkey::new_unique());
+            let mut vault = 0;
+            let mut released = 0;
+            let mut funded = 0;
+            let mut withdrawn = 0;
+
+            for _ in 0..200 {
+                let op = rng.op(1_000);
+                let before = reward;
+
+                match apply(&mut reward, &mut vault, op) {
+                    Ok(()) => match op {
+                        Op::Fund(amount) => funded += amount,
+                        Op::Release(amount) => released += amount,
+                        Op::Withdraw(amount) => withdrawn += amount,
+                        Op::Reserve(_) => {}
+                    },
+                    // Failed operations must leave the reward untouched.
+                    Err(_) => assert_eq!(reward, before, "{:?} changed the reward", op),
+                }
+
+                assert_eq!(reward.total().unwrap(), vault);
+                assert_eq!(vault, funded - released - withdrawn);
+            }
+        }
+    }
+
+    #[test]
+    fn random_sequences_never_release_unreserved_tokens() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..500 {
+            let mut reward = Reward::new(Pubkey::new_unique());
+            let mut vault = 0;
+            let mut reserved = 0;
+            let mut released = 0;
+
+            for _ in 0..200 {
+                let op = rng.op(1_000);
+
+                if apply(&mut reward, &mut vault, op).is_ok() {
+                    match op {
+                        Op::Reserve(amount) => reserved += amount,
+                        Op::Release(amount) => released += amount,
+                        _ => {}
+                    }
+                }
+
+                assert!(released <= reserved);
+                assert_eq!(reward.reserved, reserved - released);
+            }
+        }
+    }
+}
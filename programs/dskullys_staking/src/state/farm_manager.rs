@@ -1,12 +1,135 @@
 use anchor_lang::prelude::*;
 
+use crate::error::StakingError;
+
+use super::Farm;
+
 #[account]
 pub struct FarmManager {
     pub farm: Pubkey,
     pub authority: Pubkey,
+    // Bitmask of the actions this manager is allowed to perform.
+    pub permissions: u8,
 }
 
 impl FarmManager {
-    pub const LEN: usize = 32 + 32;
+    pub const LEN: usize = 32 + 32 + 1;
+    /// Length of the managers created before permissions were added, which must be migrated
+    /// with `migrate_farm_manager`.
+    pub const LEGACY_LEN: usize = 32 + 32;
     pub const PREFIX: &'static [u8] = b"farm_manager";
+
+    /// Add and remove whitelists.
+    pub const WHITELIST: u8 = 1 << 0;
+    /// Fund the reward vault.
+    pub const FUND: u8 = 1 << 1;
+    /// Unstake gems on behalf of their owners.
+    pub const FORCE_UNSTAKE: u8 = 1 << 2;
+    /// Change reward rates, buffs and reward windows.
+    pub const RATES: u8 = 1 << 3;
+
+    pub const ALL: u8 = Self::WHITELIST | Self::FUND | Self::FORCE_UNSTAKE | Self::RATES;
+
+    pub fn can(&self, permissions: u8) -> bool {
+        self.permissions & permissions == permissions
+    }
+
+    /// Checks that `authority` can perform `permissions` on `farm`, either as the farm authority
+    /// or through its manager `account`, which isn't initialized when the authority signs. The
+    /// manager address must have been checked by the caller.
+    pub fn authorize(
+        account: &AccountInfo,
+        farm: &Farm,
+        authority: &Pubkey,
+        permissions: u8,
+    ) -> Result<()> {
+        if *authority == farm.authority {
+            return Ok(());
+        }
+
+        require!(!account.data_is_empty(), StakingError::MissingPermission);
+        let manager: Account<Self> = Account::try_from(account)?;
+        require!(manager.can(permissions), StakingError::MissingPermission);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+    use crate::state::Reward;
+
+    fn legacy_account(farm: Pubkey, authority: Pubkey) -> Vec<u8> {
+        let mut data = FarmManager::discriminator().to_vec();
+        data.extend_from_slice(farm.as_ref());
+        data.extend_from_slice(authority.as_ref());
+        data
+    }
+
+    #[test]
+    fn legacy_accounts_load_once_migrated() {
+        let farm = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let mut data = legacy_account(farm, authority);
+
+        assert_eq!(data.len(), 8 + FarmManager::LEGACY_LEN);
+        assert!(FarmManager::try_deserialize(&mut data.as_slice()).is_err());
+
+        data.resize(8 + FarmManager::LEN, 0);
+        let manager = FarmManager::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(manager.farm, farm);
+        assert_eq!(manager.authority, authority);
+        // Zero-extended managers can't do anything, so the migration grants them every
+        // permission, as they had before permissions existed.
+        assert!(!manager.can(FarmManager::WHITELIST));
+    }
+
+    #[test]
+    fn farm_authority_doesnt_need_a_manager() {
+        let authority = Pubkey::new_unique();
+        let farm = Farm::new(authority, Reward::new(Pubkey::new_unique()), 255);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut empty = vec![];
+        let missing = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut empty,
+            &crate::ID,
+            false,
+            0,
+        );
+        FarmManager::authorize(&missing, &farm, &authority, FarmManager::ALL).unwrap();
+
+        let other = Pubkey::new_unique();
+        assert!(FarmManager::authorize(&missing, &farm, &other, FarmManager::WHITELIST).is_err());
+
+        let manager = FarmManager {
+            farm: Pubkey::new_unique(),
+            authority: other,
+            permissions: FarmManager::FORCE_UNSTAKE,
+        };
+        let mut lamports = 0;
+        let mut data = vec![];
+        manager.try_serialize(&mut data).unwrap();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        FarmManager::authorize(&info, &farm, &other, FarmManager::FORCE_UNSTAKE).unwrap();
+        assert!(FarmManager::authorize(&info, &farm, &other, FarmManager::FUND).is_err());
+    }
 }
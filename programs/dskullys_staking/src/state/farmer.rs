@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 
 use crate::{error::StakingError, utils::now_ts};
 
-use super::Farm;
+use super::{AccrualPolicy, Farm, Reward, SecondaryReward, StakeReceipt, WhitelistProof};
 
 #[account]
 pub struct Farmer {
@@ -12,11 +12,21 @@ pub struct Farmer {
     total_reward_rate: u64,
     last_update: u64,
     bump: [u8; 1],
-    _reserved: [u8; 64],
+    // Rewards that could not be accrued because the farm ran out of funds.
+    reward_shortfall: u64,
+    // Reserved in the farm's secondary reward, like `accrued_rewards` in its main reward.
+    secondary_accrued_rewards: u64,
+    secondary_reward_rate: u64,
+    // `Farm::paused_seconds` at the last update.
+    paused_seconds: u64,
+    // Secondary reward rate the farmer earns at, counted in `SecondaryReward::earning_rate`.
+    // Follows `secondary_reward_rate` through `sync_secondary_rate`.
+    secondary_earning_rate: u64,
+    _reserved2: [u8; 24],
 }
 
 impl Farmer {
-    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 1 + 64;
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 8 + 24;
     pub const PREFIX: &'static [u8] = b"farmer";
 
     pub fn seeds(&self) -> [&[u8]; 4] {
@@ -28,20 +38,43 @@ impl Farmer {
         ]
     }
 
-    pub fn new(farm: Pubkey, owner: Pubkey, bump: u8) -> Result<Self> {
-        Ok(Self {
-            farm,
+    /// Starts from the farm's current paused seconds, pauses that ended before the farmer was
+    /// created don't count against its rewards.
+    pub fn new(farm: &Account<Farm>, owner: Pubkey, bump: u8, now: u64) -> Self {
+        Self {
+            farm: farm.key(),
             owner,
             accrued_rewards: 0,
             total_reward_rate: 0,
-            last_update: now_ts()?,
+            last_update: now,
             bump: [bump],
-            _reserved: [0; 64],
-        })
+            reward_shortfall: 0,
+            secondary_accrued_rewards: 0,
+            secondary_reward_rate: 0,
+            paused_seconds: farm.paused_seconds(now),
+            secondary_earning_rate: 0,
+            _reserved2: [0; 24],
+        }
+    }
+
+    /// Pays the accrued main rewards. The secondary ones are accrued too, they're paid by
+    /// `claim_secondary_accrued`.
+    pub fn claim_accrued(
+        &mut self,
+        farm: &mut Farm,
+        secondary_reward: Option<&mut Reward>,
+    ) -> Result<u64> {
+        self.claim_accrued_at(farm, secondary_reward, now_ts()?)
     }
 
-    pub fn claim_accrued(&mut self, farm: &mut Farm) -> Result<u64> {
-        self.update_accrued_rewards(farm)?;
+    fn claim_accrued_at(
+        &mut self,
+        farm: &mut Farm,
+        secondary_reward: Option<&mut Reward>,
+        now: u64,
+    ) -> Result<u64> {
+        let policy = farm.accrual_policy;
+        self.accrue_at(farm, secondary_reward, policy, now)?;
 
         let reward = self.accrued_rewards;
 
@@ -52,41 +85,606 @@ impl Farmer {
         Ok(reward)
     }
 
-    pub fn update_accrued_rewards(&mut self, farm: &mut Farm) -> Result<()> {
-        let now = now_ts()?;
-        let elapsed = now.saturating_sub(self.last_update);
-        let increment = self.total_reward_rate * elapsed;
+    /// Pays the accrued secondary rewards, which were reserved in `reward`. Must be called
+    /// after the rewards were updated, e.g. by `claim_accrued`.
+    pub fn claim_secondary_accrued(&mut self, reward: &mut Reward) -> Result<u64> {
+        let amount = self.secondary_accrued_rewards;
+
+        reward.try_release(amount)?;
+
+        self.secondary_accrued_rewards = 0;
+
+        Ok(amount)
+    }
+
+    /// Accrues the rewards earned since the last update. Secondary rewards only accrue when the
+    /// farm's `secondary_reward` is given, farms without one don't pay any.
+    pub fn update_accrued_rewards(
+        &mut self,
+        farm: &mut Farm,
+        secondary_reward: Option<&mut Reward>,
+    ) -> Result<()> {
+        let policy = farm.accrual_policy;
+        self.accrue(farm, secondary_reward, policy)
+    }
+
+    /// Same as `update_accrued_rewards`, but never fails because the farm ran out of rewards, so
+    /// the gems can always be unstaked.
+    pub fn settle_accrued_rewards(
+        &mut self,
+        farm: &mut Farm,
+        secondary_reward: Option<&mut Reward>,
+    ) -> Result<()> {
+        let policy = match farm.accrual_policy {
+            AccrualPolicy::Fail => AccrualPolicy::Partial,
+            policy => policy,
+        };
+        self.accrue(farm, secondary_reward, policy)
+    }
+
+    pub fn accrued_rewards(&self) -> u64 {
+        self.accrued_rewards
+    }
+
+    pub fn total_reward_rate(&self) -> u64 {
+        self.total_reward_rate
+    }
+
+    pub fn last_update(&self) -> u64 {
+        self.last_update
+    }
+
+    pub fn reward_shortfall(&self) -> u64 {
+        self.reward_shortfall
+    }
+
+    /// Nothing is staked and every reward was claimed, so the account can be closed.
+    pub fn is_empty(&self) -> bool {
+        self.total_reward_rate == 0
+            && self.accrued_rewards == 0
+            && self.secondary_reward_rate == 0
+            && self.secondary_earning_rate == 0
+            && self.secondary_accrued_rewards == 0
+    }
+
+    pub fn secondary_accrued_rewards(&self) -> u64 {
+        self.secondary_accrued_rewards
+    }
+
+    pub fn secondary_reward_rate(&self) -> u64 {
+        self.secondary_reward_rate
+    }
+
+    /// Rewards that claiming at `now` would pay out, without modifying any account. Fails
+    /// whenever the claim itself would fail.
+    pub fn pending_rewards(&self, farm: &Farm, now: u64) -> Result<u64> {
+        let (accrued, _) = self.accrual(farm, farm.accrual_policy, now)?;
+
+        if accrued > farm.reward.available {
+            return err!(StakingError::CouldNotReserveReward);
+        }
+
+        let pending = self
+            .accrued_rewards
+            .checked_add(accrued)
+            .ok_or(StakingError::ArithmeticError)?;
 
-        if increment > 0 {
+        Ok(pending)
+    }
+
+    /// Secondary rewards that claiming at `now` would pay out, without modifying any account.
+    /// Fails whenever the claim itself would fail.
+    pub fn pending_secondary_rewards(
+        &self,
+        farm: &Farm,
+        secondary_reward: &Reward,
+        now: u64,
+    ) -> Result<u64> {
+        let (accrued, _) =
+            self.secondary_accrual(farm, secondary_reward, farm.accrual_policy, now)?;
+
+        if accrued > secondary_reward.available {
+            return err!(StakingError::CouldNotReserveReward);
+        }
+
+        let pending = self
+            .secondary_accrued_rewards
+            .checked_add(accrued)
+            .ok_or(StakingError::ArithmeticError)?;
+
+        Ok(pending)
+    }
+
+    /// Same as `accrual`, for the secondary rewards.
+    fn secondary_accrual(
+        &self,
+        farm: &Farm,
+        secondary_reward: &Reward,
+        policy: AccrualPolicy,
+        now: u64,
+    ) -> Result<(u64, u64)> {
+        let increment = self
+            .secondary_earning_rate
+            .checked_mul(self.elapsed(farm, now))
+            .ok_or(StakingError::ArithmeticError)?;
+
+        Ok(apply_policy(increment, secondary_reward.available, policy))
+    }
+
+    /// Seconds since the last update during which the farm emitted rewards. Both durations are
+    /// clipped to the reward window, see `Farm::paused_seconds`.
+    fn elapsed(&self, farm: &Farm, now: u64) -> u64 {
+        let paused = farm.paused_seconds(now).saturating_sub(self.paused_seconds);
+
+        farm.reward_duration(self.last_update, now).saturating_sub(paused)
+    }
+
+    /// Rewards earned since the last update that `policy` lets accrue, and the ones that were
+    /// lost because the farm ran out of funds.
+    fn accrual(&self, farm: &Farm, policy: AccrualPolicy, now: u64) -> Result<(u64, u64)> {
+        let elapsed = self.elapsed(farm, now);
+        let increment = self
+            .total_reward_rate
+            .checked_mul(elapsed)
+            .ok_or(StakingError::ArithmeticError)?;
+
+        Ok(apply_policy(increment, farm.reward.available, policy))
+    }
+
+    fn accrue(
+        &mut self,
+        farm: &mut Farm,
+        secondary_reward: Option<&mut Reward>,
+        policy: AccrualPolicy,
+    ) -> Result<()> {
+        self.accrue_at(farm, secondary_reward, policy, now_ts()?)
+    }
+
+    fn accrue_at(
+        &mut self,
+        farm: &mut Farm,
+        secondary_reward: Option<&mut Reward>,
+        policy: AccrualPolicy,
+        now: u64,
+    ) -> Result<()> {
+        let (accrued, shortfall) = self.accrual(farm, policy, now)?;
+
+        if accrued > 0 {
             // Before updating, we try to reserve the reward.
-            farm.reward.try_reserve(increment)?;
+            farm.reward.try_reserve(accrued)?;
 
             self.accrued_rewards = self
                 .accrued_rewards
-                .checked_add(increment)
+                .checked_add(accrued)
+                .ok_or(StakingError::ArithmeticError)?;
+        }
+
+        if shortfall > 0 {
+            msg!("Farm is out of rewards, {} tokens were not accrued.", shortfall);
+
+            self.reward_shortfall = self
+                .reward_shortfall
+                .checked_add(shortfall)
                 .ok_or(StakingError::ArithmeticError)?;
         }
 
+        if let Some(secondary_reward) = secondary_reward {
+            let (secondary_accrued, secondary_shortfall) =
+                self.secondary_accrual(farm, secondary_reward, policy, now)?;
+
+            secondary_reward.try_reserve(secondary_accrued)?;
+
+            self.secondary_accrued_rewards = self
+                .secondary_accrued_rewards
+                .checked_add(secondary_accrued)
+                .ok_or(StakingError::ArithmeticError)?;
+
+            if secondary_shortfall > 0 {
+                msg!(
+                    "Farm is out of secondary rewards, {} tokens were not accrued.",
+                    secondary_shortfall
+                );
+            }
+        }
+
         self.last_update = now;
+        self.paused_seconds = farm.paused_seconds(now);
+
+        Ok(())
+    }
+
+    /// Settles the rewards earned so far and stops `receipt` from earning any more.
+    pub fn end_stake(
+        &mut self,
+        farm: &mut Farm,
+        secondary_reward: Option<&mut Reward>,
+        receipt: &mut StakeReceipt,
+        now: u64,
+    ) -> Result<()> {
+        self.settle_accrued_rewards(farm, secondary_reward)?;
+        self.decrease_reward_rate(receipt)?;
+
+        receipt.end_ts = Some(now);
+
+        Ok(())
+    }
+
+    /// Lets the gems of `receipt` leave the farm. Running stakes without a cooldown stop
+    /// earning, stakes with one must have finished cooling down.
+    pub fn unstake(
+        &mut self,
+        farm: &mut Farm,
+        secondary_reward: Option<&mut Reward>,
+        receipt: &mut StakeReceipt,
+        now: u64,
+    ) -> Result<()> {
+        if receipt.is_running() {
+            // Gems without a cooldown are unstaked in a single step, the others must go through
+            // `begin_unstake` first.
+            receipt.require_unlocked(now)?;
+            require!(
+                receipt.unstake_cooldown == 0,
+                StakingError::CooldownIsNotOver
+            );
+
+            self.end_stake(farm, secondary_reward, receipt, now)?;
+        } else {
+            require!(receipt.is_cooling_down(), StakingError::GemNotStaked);
+            receipt.finish_cooldown(now)?;
+        }
+
+        farm.remove_stake(receipt)?;
+
+        Ok(())
+    }
+
+    /// Lets the gems of `receipt` leave a farm that was closed. Nothing is left to pay rewards
+    /// from, so the stake isn't settled, and locks and cooldowns no longer apply.
+    pub fn withdraw_orphaned(&mut self, receipt: &mut StakeReceipt, now: u64) -> Result<()> {
+        if receipt.is_running() {
+            self.decrease_reward_rate(receipt)?;
+            receipt.end_ts = Some(now);
+        } else {
+            require!(receipt.is_cooling_down(), StakingError::GemNotStaked);
+            receipt.cooldown_end_ts = 0;
+        }
 
         Ok(())
     }
 
-    pub fn increase_reward_rate(&mut self, increment: u64) -> Result<()> {
+    /// Moves a running stake to the current rates of its whitelist, if they changed. The
+    /// rewards must have been settled first, they were earned at the old rates. Returns
+    /// whether the stake was refreshed.
+    pub fn refresh_stake(
+        &mut self,
+        receipt: &mut StakeReceipt,
+        whitelist: Pubkey,
+        whitelist_proof: &WhitelistProof,
+        buff_factor: u64,
+    ) -> Result<bool> {
+        if !receipt.is_running() || !receipt.is_outdated(whitelist, whitelist_proof)? {
+            return Ok(false);
+        }
+
+        self.decrease_reward_rate(receipt)?;
+        receipt.refresh(whitelist_proof, buff_factor)?;
+        self.increase_reward_rate(receipt)?;
+
+        Ok(true)
+    }
+
+    /// Starts earning the rewards of `receipt`.
+    pub fn increase_reward_rate(&mut self, receipt: &StakeReceipt) -> Result<()> {
         self.total_reward_rate = self
             .total_reward_rate
-            .checked_add(increment)
+            .checked_add(receipt.reward_rate)
+            .ok_or(StakingError::ArithmeticError)?;
+
+        self.secondary_reward_rate = self
+            .secondary_reward_rate
+            .checked_add(receipt.secondary_reward_rate)
             .ok_or(StakingError::ArithmeticError)?;
 
         Ok(())
     }
 
-    pub fn decrease_reward_rate(&mut self, decrement: u64) -> Result<()> {
+    /// Stops earning the rewards of `receipt`.
+    pub fn decrease_reward_rate(&mut self, receipt: &StakeReceipt) -> Result<()> {
         self.total_reward_rate = self
             .total_reward_rate
-            .checked_sub(decrement)
+            .checked_sub(receipt.reward_rate)
+            .ok_or(StakingError::ArithmeticError)?;
+
+        self.secondary_reward_rate = self
+            .secondary_reward_rate
+            .checked_sub(receipt.secondary_reward_rate)
+            .ok_or(StakingError::ArithmeticError)?;
+
+        Ok(())
+    }
+
+    /// Moves the rate the farmer earns the secondary reward at to its current secondary reward
+    /// rate, keeping the total of `secondary_reward` up to date. The farmer must have been
+    /// updated with `secondary_reward` at the current time, since the new rate applies from then.
+    /// Farmers that staked while the farm had no secondary reward start earning it once synced.
+    pub fn sync_secondary_rate(&mut self, secondary_reward: &mut SecondaryReward) -> Result<()> {
+        secondary_reward.earning_rate = secondary_reward
+            .earning_rate
+            .checked_sub(self.secondary_earning_rate)
+            .and_then(|rate| rate.checked_add(self.secondary_reward_rate))
             .ok_or(StakingError::ArithmeticError)?;
 
+        self.secondary_earning_rate = self.secondary_reward_rate;
+
         Ok(())
     }
 }
+
+/// Part of `increment` that `policy` lets accrue when `available` tokens are left, and the part
+/// that is lost.
+fn apply_policy(increment: u64, available: u64, policy: AccrualPolicy) -> (u64, u64) {
+    let accrued = match policy {
+        AccrualPolicy::Fail => increment,
+        AccrualPolicy::Partial => increment.min(available),
+        AccrualPolicy::Skip if increment > available => 0,
+        AccrualPolicy::Skip => increment,
+    };
+
+    (accrued, increment - accrued)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{FarmState, Reward, SecondaryReward, WhitelistType};
+
+    fn farm(policy: AccrualPolicy, funds: u64) -> Farm {
+        let mut farm = Farm::new(Pubkey::new_unique(), Reward::new(Pubkey::new_unique()), 255);
+        farm.accrual_policy = policy;
+        farm.reward.try_fund(funds).unwrap();
+        farm
+    }
+
+    fn farmer(farm: &Farm, reward_rate: u64) -> Farmer {
+        Farmer {
+            farm: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            accrued_rewards: 0,
+            total_reward_rate: reward_rate,
+            last_update: 0,
+            bump: [255],
+            reward_shortfall: 0,
+            secondary_accrued_rewards: 0,
+            secondary_reward_rate: 0,
+            paused_seconds: farm.paused_seconds(0),
+            secondary_earning_rate: 0,
+            _reserved2: [0; 24],
+        }
+    }
+
+    /// A funded secondary reward that `farmer` earns at `rate`.
+    fn secondary_reward(farmer: &mut Farmer, rate: u64, funds: u64) -> SecondaryReward {
+        let mut secondary_reward = SecondaryReward {
+            farm: farmer.farm,
+            reward: Reward::new(Pubkey::new_unique()),
+            bump: [255],
+            earning_rate: 0,
+        };
+        secondary_reward.reward.try_fund(funds).unwrap();
+
+        farmer.secondary_reward_rate = rate;
+        farmer.sync_secondary_rate(&mut secondary_reward).unwrap();
+
+        secondary_reward
+    }
+
+    /// What claiming at `now` pays, checking `pending_rewards` predicted it.
+    fn claim(farmer: &Farmer, farm: &Farm, now: u64) -> Option<u64> {
+        let pending = farmer.pending_rewards(farm, now).ok();
+        let claimed = farmer
+            .clone()
+            .claim_accrued_at(&mut farm.clone(), None, now)
+            .ok();
+
+        assert_eq!(pending, claimed);
+        claimed
+    }
+
+    #[test]
+    fn pending_rewards_match_the_claim_under_every_policy() {
+        use AccrualPolicy::*;
+
+        for (policy, enough, short) in [
+            (Fail, Some(500), None),
+            (Partial, Some(500), Some(1_000)),
+            (Skip, Some(500), Some(0)),
+        ] {
+            let farm = farm(policy, 1_000);
+            let farmer = farmer(&farm, 10);
+
+            assert_eq!(claim(&farmer, &farm, 50), enough, "{:?}", policy);
+            assert_eq!(claim(&farmer, &farm, 200), short, "{:?}", policy);
+        }
+    }
+
+    #[test]
+    fn pending_rewards_match_the_claim_outside_the_reward_window() {
+        let mut farm = farm(AccrualPolicy::Fail, 10_000);
+        farm.reward_start_ts = 20;
+        farm.reward_end_ts = 60;
+        let farmer = farmer(&farm, 10);
+
+        assert_eq!(claim(&farmer, &farm, 10), Some(0));
+        assert_eq!(claim(&farmer, &farm, 30), Some(100));
+        assert_eq!(claim(&farmer, &farm, 100), Some(400));
+    }
+
+    #[test]
+    fn pending_rewards_match_the_claim_across_a_pause() {
+        let mut farm = farm(AccrualPolicy::Fail, 10_000);
+        let farmer = farmer(&farm, 10);
+
+        farm.set_state(FarmState::RewardsPaused, 30);
+        assert_eq!(claim(&farmer, &farm, 50), Some(300));
+
+        farm.set_state(FarmState::Active, 70);
+        assert_eq!(claim(&farmer, &farm, 100), Some(600));
+    }
+
+    #[test]
+    fn pauses_after_the_reward_window_dont_take_rewards() {
+        let mut farm = farm(AccrualPolicy::Fail, 10_000);
+        farm.reward_end_ts = 60;
+        let farmer = farmer(&farm, 10);
+
+        farm.set_state(FarmState::RewardsPaused, 80);
+        assert_eq!(claim(&farmer, &farm, 100), Some(600));
+
+        farm.set_state(FarmState::Active, 150);
+        assert_eq!(claim(&farmer, &farm, 200), Some(600));
+
+        // A pause across the end only takes the part inside the window.
+        let mut farm = self::farm(AccrualPolicy::Fail, 10_000);
+        farm.reward_end_ts = 60;
+        farm.set_state(FarmState::RewardsPaused, 40);
+        farm.set_state(FarmState::Active, 150);
+        assert_eq!(claim(&farmer, &farm, 200), Some(400));
+    }
+
+    #[test]
+    fn secondary_rewards_are_reserved_under_the_farm_policy() {
+        use AccrualPolicy::*;
+
+        for (policy, claimed) in [(Fail, None), (Partial, Some(300)), (Skip, Some(0))] {
+            let farm = farm(policy, 10_000);
+            let mut farmer = farmer(&farm, 10);
+            let secondary_reward = secondary_reward(&mut farmer, 5, 300).reward;
+
+            // 50s at 5 tokens/sec fit in the vault, 100s don't.
+            for (now, expected) in [(50, Some(250)), (100, claimed)] {
+                let pending = farmer
+                    .pending_secondary_rewards(&farm, &secondary_reward, now)
+                    .ok();
+
+                let mut farmer = farmer.clone();
+                let mut reward = secondary_reward;
+                let paid = farmer
+                    .claim_accrued_at(&mut farm.clone(), Some(&mut reward), now)
+                    .and_then(|_| farmer.claim_secondary_accrued(&mut reward))
+                    .ok();
+
+                assert_eq!(pending, expected, "{:?}", policy);
+                assert_eq!(paid, expected, "{:?}", policy);
+            }
+        }
+    }
+
+    #[test]
+    fn accrued_secondary_rewards_cant_be_withdrawn() {
+        let mut farm = farm(AccrualPolicy::Fail, 10_000);
+        let mut farmer = farmer(&farm, 0);
+        let mut secondary_reward = secondary_reward(&mut farmer, 5, 1_000).reward;
+
+        farmer
+            .accrue_at(&mut farm, Some(&mut secondary_reward), AccrualPolicy::Fail, 100)
+            .unwrap();
+        assert_eq!(secondary_reward.reserved, 500);
+        assert!(secondary_reward.try_withdraw(501).is_err());
+
+        secondary_reward.try_withdraw(500).unwrap();
+        assert_eq!(farmer.claim_secondary_accrued(&mut secondary_reward).unwrap(), 500);
+        assert_eq!(secondary_reward.total().unwrap(), 0);
+    }
+
+    #[test]
+    fn new_farmers_arent_charged_for_past_pauses() {
+        let mut farm = farm(AccrualPolicy::Fail, 10_000);
+        farm.set_state(FarmState::RewardsPaused, 30);
+        farm.set_state(FarmState::Active, 70);
+
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![];
+        farm.try_serialize(&mut data).unwrap();
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let farm_account: Account<Farm> = Account::try_from(&info).unwrap();
+
+        let mut farmer = Farmer::new(&farm_account, Pubkey::new_unique(), 255, 100);
+        farmer.total_reward_rate = 10;
+
+        assert_eq!(farmer.farm, key);
+        assert_eq!(claim(&farmer, &farm, 150), Some(500));
+    }
+
+    #[test]
+    fn stakes_left_out_by_an_early_migration_can_be_withdrawn() {
+        let mut farm = farm(AccrualPolicy::Fail, 0);
+        farm.stakes_counted = false;
+
+        let whitelist_proof = WhitelistProof::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            10,
+            0,
+            WhitelistType::Mint,
+            0,
+            0,
+        );
+        let mut legacy = StakeReceipt::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            1,
+            Pubkey::default(),
+            &whitelist_proof,
+            0,
+        )
+        .unwrap();
+        let mut farmer = farmer(&farm, legacy.reward_rate);
+
+        // Finishing before the stake was migrated leaves it out of the count, so the farm can
+        // be closed with the gem still staked.
+        farm.stakes_counted = true;
+        assert_eq!(farm.active_stakes, 0);
+
+        farmer.withdraw_orphaned(&mut legacy, 100).unwrap();
+        assert!(!legacy.is_running() && !legacy.is_cooling_down());
+        assert_eq!(farmer.total_reward_rate, 0);
+        assert!(farmer.withdraw_orphaned(&mut legacy, 100).is_err());
+    }
+
+    #[test]
+    fn secondary_rewards_are_earned_once_counted() {
+        let mut farm = farm(AccrualPolicy::Fail, 10_000);
+        let mut farmer = farmer(&farm, 0);
+        let mut secondary_reward = secondary_reward(&mut farmer, 0, 10_000);
+
+        // Staked before the farm had a secondary reward, the rate isn't counted yet.
+        farmer.secondary_reward_rate = 5;
+        let reward = Some(&mut secondary_reward.reward);
+        farmer.accrue_at(&mut farm, reward, AccrualPolicy::Fail, 100).unwrap();
+        assert_eq!(farmer.secondary_accrued_rewards(), 0);
+
+        farmer.sync_secondary_rate(&mut secondary_reward).unwrap();
+        assert_eq!(secondary_reward.earning_rate, 5);
+
+        // Unstaking settles, then takes the rate out of the total.
+        let reward = Some(&mut secondary_reward.reward);
+        farmer.accrue_at(&mut farm, reward, AccrualPolicy::Fail, 200).unwrap();
+        farmer.secondary_reward_rate = 0;
+        farmer.sync_secondary_rate(&mut secondary_reward).unwrap();
+
+        assert_eq!(farmer.secondary_accrued_rewards(), 500);
+        assert_eq!(secondary_reward.reward.reserved, 500);
+        assert_eq!(secondary_reward.earning_rate, 0);
+    }
+}
@@ -1,11 +1,17 @@
+pub mod authority_transfer;
+pub mod buff_proof;
 pub mod farm;
 pub mod farm_manager;
 pub mod farmer;
+pub mod secondary_reward;
 pub mod stake_receipt;
 pub mod whitelist_proof;
 
+pub use authority_transfer::*;
+pub use buff_proof::*;
 pub use farm::*;
 pub use farm_manager::*;
 pub use farmer::*;
+pub use secondary_reward::*;
 pub use stake_receipt::*;
 pub use whitelist_proof::*;
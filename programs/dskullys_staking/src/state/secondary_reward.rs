@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use super::Reward;
+
+/// A second token paid by the farm on top of its main reward.
+///
+/// Farms can't grow, so the secondary reward bookkeeping lives in its own account. It works like
+/// the main reward: farmers reserve what they accrue, and only the rest can be withdrawn.
+#[account]
+pub struct SecondaryReward {
+    pub farm: Pubkey,
+    pub reward: Reward,
+    pub bump: [u8; 1],
+    // Sum of the rates farmers earn it at. Every farmer was settled once it's zero, see
+    // `Farmer::sync_secondary_rate`.
+    pub earning_rate: u64,
+}
+
+impl SecondaryReward {
+    pub const LEN: usize = 32 + Reward::LEN + 1 + 8;
+    pub const PREFIX: &'static [u8] = b"secondary_reward";
+
+    /// Loads the farm's secondary reward from an account that may not have been initialized, in
+    /// which case the farm has none. The address must have been checked by the caller.
+    pub fn load<'info>(account: &AccountInfo<'info>) -> Result<Option<Account<'info, Self>>> {
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Account::try_from(account)?))
+    }
+}
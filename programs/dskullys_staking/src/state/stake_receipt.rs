@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::error::StakingError;
+
+use super::WhitelistProof;
+
 #[account]
 pub struct StakeReceipt {
     pub farmer: Pubkey,
@@ -8,11 +12,22 @@ pub struct StakeReceipt {
     pub end_ts: Option<u64>,
     pub amount: u64,
     pub reward_rate: u64,
-    _reserved: [u8; 64],
+    // The gem can't be unstaked before this timestamp.
+    pub lock_end_ts: u64,
+    // Seconds between beginning and finishing an unstake.
+    pub unstake_cooldown: u32,
+    // When the gems can be withdrawn after beginning an unstake. Zero when not cooling down.
+    pub cooldown_end_ts: u64,
+    // Whitelist rate epoch the reward rate was computed at.
+    pub rate_epoch: u32,
+    pub secondary_reward_rate: u64,
+    // Whitelist the gem was staked through, the only one that can change its rates. Default for
+    // stakes made before it was stored, until a farm manager migrates them.
+    pub whitelist: Pubkey,
 }
 
 impl StakeReceipt {
-    pub const LEN: usize = 32 + 32 + 8 + 9 + 8 + 8 + 64;
+    pub const LEN: usize = 32 + 32 + 8 + 9 + 8 + 8 + 8 + 4 + 8 + 4 + 8 + 32;
     pub const PREFIX: &'static [u8] = b"stake_receipt";
 
     pub fn new(
@@ -20,20 +35,266 @@ impl StakeReceipt {
         mint: Pubkey,
         start_ts: u64,
         amount: u64,
-        reward_rate: u64,
-    ) -> Self {
-        Self {
+        whitelist: Pubkey,
+        whitelist_proof: &WhitelistProof,
+        buff_factor: u64,
+    ) -> Result<Self> {
+        Ok(Self {
             farmer,
             mint,
             start_ts,
             end_ts: None,
             amount,
-            reward_rate,
-            _reserved: [0; 64],
-        }
+            reward_rate: whitelist_proof.reward_rate_for(amount, buff_factor)?,
+            lock_end_ts: start_ts.saturating_add(whitelist_proof.min_stake_duration),
+            unstake_cooldown: whitelist_proof.unstake_cooldown,
+            cooldown_end_ts: 0,
+            rate_epoch: whitelist_proof.rate_epoch,
+            secondary_reward_rate: whitelist_proof.secondary_reward_rate_for(amount, buff_factor)?,
+            whitelist,
+        })
     }
 
     pub fn is_running(&self) -> bool {
         self.end_ts.is_none()
     }
+
+    /// The receipt stopped earning rewards but the gems are still in the farmer vault.
+    pub fn is_cooling_down(&self) -> bool {
+        self.cooldown_end_ts != 0
+    }
+
+    pub fn require_unlocked(&self, now: u64) -> Result<()> {
+        require_gte!(now, self.lock_end_ts, StakingError::GemStillLocked);
+        Ok(())
+    }
+
+    pub fn start_cooldown(&mut self, now: u64) {
+        self.cooldown_end_ts = now.saturating_add(self.unstake_cooldown.into());
+    }
+
+    pub fn finish_cooldown(&mut self, now: u64) -> Result<()> {
+        require_gte!(now, self.cooldown_end_ts, StakingError::CooldownIsNotOver);
+        self.cooldown_end_ts = 0;
+        Ok(())
+    }
+
+    /// The stake was made after receipts started storing their whitelist, or was migrated.
+    pub fn is_migrated(&self) -> bool {
+        self.whitelist != Pubkey::default()
+    }
+
+    /// Stores the whitelist of a stake made before receipts kept track of it.
+    pub fn migrate(&mut self, whitelist: Pubkey) -> Result<()> {
+        require!(!self.is_migrated(), StakingError::AccountAlreadyMigrated);
+        self.whitelist = whitelist;
+        Ok(())
+    }
+
+    /// Whether `whitelist` changed its rates since the stake last picked them up. Fails for any
+    /// whitelist but the one the gem was staked through.
+    pub fn is_outdated(&self, whitelist: Pubkey, whitelist_proof: &WhitelistProof) -> Result<bool> {
+        require_keys_eq!(whitelist, self.whitelist, StakingError::WhitelistMismatch);
+        Ok(self.rate_epoch < whitelist_proof.rate_epoch)
+    }
+
+    /// Moves the stake to the whitelist's current rates.
+    pub fn refresh(&mut self, whitelist_proof: &WhitelistProof, buff_factor: u64) -> Result<()> {
+        self.reward_rate = whitelist_proof.reward_rate_for(self.amount, buff_factor)?;
+        self.secondary_reward_rate =
+            whitelist_proof.secondary_reward_rate_for(self.amount, buff_factor)?;
+        self.rate_epoch = whitelist_proof.rate_epoch;
+
+        Ok(())
+    }
+
+    /// Adds fungible tokens to a running stake. The whole position moves to the whitelist's
+    /// current rates, and the lock is pushed back so the new tokens serve the full minimum
+    /// duration. The lock covers the whole position: topping up also keeps the tokens that were
+    /// already staked locked until the new end, there is no lock per deposit.
+    pub fn add_amount(
+        &mut self,
+        amount: u64,
+        whitelist: Pubkey,
+        whitelist_proof: &WhitelistProof,
+        buff_factor: u64,
+        now: u64,
+    ) -> Result<()> {
+        if self.is_migrated() {
+            require_keys_eq!(whitelist, self.whitelist, StakingError::WhitelistMismatch);
+        }
+
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(StakingError::ArithmeticError)?;
+        self.whitelist = whitelist;
+        self.refresh(whitelist_proof, buff_factor)?;
+        self.lock_end_ts = self
+            .lock_end_ts
+            .max(now.saturating_add(whitelist_proof.min_stake_duration));
+
+        Ok(())
+    }
+
+    /// Takes `amount` tokens out of a running stake, lowering its rates proportionally. At
+    /// least one token must stay staked, full unstakes go through `unstake`.
+    pub fn remove_amount(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount > 0 && amount < self.amount,
+            StakingError::InvalidUnstakeAmount
+        );
+
+        let remaining = self.amount - amount;
+
+        self.reward_rate = scale_rate(self.reward_rate, remaining, self.amount)?;
+        self.secondary_reward_rate =
+            scale_rate(self.secondary_reward_rate, remaining, self.amount)?;
+        self.amount = remaining;
+
+        Ok(())
+    }
+}
+
+/// `rate * remaining / amount`, rounded down.
+fn scale_rate(rate: u64, remaining: u64, amount: u64) -> Result<u64> {
+    let rate = (rate as u128)
+        .checked_mul(remaining as u128)
+        .and_then(|r| r.checked_div(amount as u128))
+        .ok_or(StakingError::ArithmeticError)?;
+
+    u64::try_from(rate).map_err(|_| error!(StakingError::ArithmeticError))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::WhitelistType;
+
+    fn whitelist(reward_rate: u64) -> WhitelistProof {
+        WhitelistProof::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            reward_rate,
+            0,
+            WhitelistType::Mint,
+            0,
+            0,
+        )
+    }
+
+    fn receipt(whitelist: Pubkey, whitelist_proof: &WhitelistProof) -> StakeReceipt {
+        StakeReceipt::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+            10,
+            whitelist,
+            whitelist_proof,
+            0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn stakes_follow_the_rate_updates_of_their_whitelist() {
+        let key = Pubkey::new_unique();
+        let mut proof = whitelist(1);
+        let mut stake = receipt(key, &proof);
+
+        assert!(!stake.is_outdated(key, &proof).unwrap());
+
+        proof.update_reward_rate(3, 0).unwrap();
+        assert!(stake.is_outdated(key, &proof).unwrap());
+
+        stake.refresh(&proof, 0).unwrap();
+        assert_eq!(stake.reward_rate, 30);
+        assert!(!stake.is_outdated(key, &proof).unwrap());
+    }
+
+    #[test]
+    fn other_whitelists_cant_refresh_a_stake() {
+        let proof = whitelist(1);
+        let mut other = whitelist(100);
+        other.update_reward_rate(100, 0).unwrap();
+        let mut stake = receipt(Pubkey::new_unique(), &proof);
+
+        assert!(stake.is_outdated(Pubkey::new_unique(), &other).is_err());
+        assert!(stake
+            .add_amount(1, Pubkey::new_unique(), &other, 0, 0)
+            .is_err());
+    }
+
+    #[test]
+    fn legacy_stakes_are_migrated_once() {
+        let key = Pubkey::new_unique();
+        let proof = whitelist(1);
+        let mut stake = receipt(Pubkey::default(), &proof);
+
+        assert!(!stake.is_migrated());
+        assert!(stake.is_outdated(key, &proof).is_err());
+
+        stake.migrate(key).unwrap();
+        assert!(stake.is_migrated());
+        assert!(!stake.is_outdated(key, &proof).unwrap());
+        assert!(stake.migrate(Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn topping_up_extends_the_lock_of_the_whole_position() {
+        let key = Pubkey::new_unique();
+        let mut proof = whitelist(1);
+        proof.min_stake_duration = 100;
+        let mut stake = receipt(key, &proof);
+        assert_eq!(stake.lock_end_ts, 100);
+
+        stake.add_amount(5, key, &proof, 0, 60).unwrap();
+        assert_eq!(stake.amount, 15);
+        assert_eq!(stake.reward_rate, 15);
+        assert_eq!(stake.lock_end_ts, 160);
+        assert!(stake.require_unlocked(100).is_err());
+        assert!(stake.require_unlocked(160).is_ok());
+
+        // A shorter duration never brings the lock forward.
+        proof.min_stake_duration = 10;
+        stake.add_amount(5, key, &proof, 0, 70).unwrap();
+        assert_eq!(stake.lock_end_ts, 160);
+    }
+
+    #[test]
+    fn removing_tokens_scales_the_rates_down() {
+        let key = Pubkey::new_unique();
+        let mut proof = whitelist(1);
+        proof.update_reward_rate(1, 1).unwrap();
+        let mut stake = receipt(key, &proof);
+        stake.refresh(&proof, 0).unwrap();
+        stake.reward_rate = 7;
+
+        // 7 * 7 / 10 rounds down.
+        stake.remove_amount(3).unwrap();
+        assert_eq!(stake.amount, 7);
+        assert_eq!(stake.reward_rate, 4);
+        assert_eq!(stake.secondary_reward_rate, 7);
+    }
+
+    #[test]
+    fn partial_unstakes_leave_tokens_staked() {
+        let mut stake = receipt(Pubkey::new_unique(), &whitelist(1));
+
+        assert!(stake.remove_amount(0).is_err());
+        assert!(stake.remove_amount(stake.amount).is_err());
+        assert!(stake.remove_amount(stake.amount + 1).is_err());
+
+        stake.remove_amount(9).unwrap();
+        assert_eq!(stake.amount, 1);
+        assert_eq!(stake.reward_rate, 1);
+    }
+
+    #[test]
+    fn scaled_rates_round_down_without_overflowing() {
+        assert_eq!(scale_rate(10, 1, 3).unwrap(), 3);
+        assert_eq!(scale_rate(2, 1, 3).unwrap(), 0);
+        assert_eq!(scale_rate(u64::MAX, u64::MAX - 1, u64::MAX).unwrap(), u64::MAX - 1);
+        assert!(scale_rate(1, 1, 0).is_err());
+    }
 }
@@ -2,7 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 use solutils::wrappers::metadata::{MetadataAccount, TokenMetadata};
 
-use crate::utils::metadata_creator;
+use crate::{
+    error::StakingError,
+    merkle,
+    utils::{calculate_reward_rate, metadata_collection, metadata_creator},
+};
 
 #[derive(Debug, Copy, Clone, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
 pub enum WhitelistType {
@@ -10,6 +14,10 @@ pub enum WhitelistType {
     Creator,
     // A fungible token address.
     Mint,
+    // A set of mints, the whitelisted address holds the merkle root.
+    MerkleRoot,
+    // A Metaplex verified collection.
+    Collection,
 }
 
 #[account]
@@ -19,33 +27,102 @@ pub struct WhitelistProof {
     // Tokens/gem/sec
     pub reward_rate: u64,
     pub ty: WhitelistType,
+    // Seconds a gem must stay staked before it can be unstaked.
+    pub min_stake_duration: u64,
+    // Seconds between beginning and finishing an unstake.
+    pub unstake_cooldown: u32,
+    // Bumped every time the reward rate changes, so outdated stakes can be found and refreshed.
+    pub rate_epoch: u32,
+    // Secondary reward tokens/gem/sec, only paid by farms with a secondary reward.
+    pub secondary_reward_rate: u64,
+    _reserved: [u8; 24],
+    _reserved2: [u8; 32],
 }
 
 impl WhitelistProof {
-    pub const LEN: usize = 32 + 32 + 8 + 1;
+    pub const LEN: usize = 32 + 32 + 8 + 1 + 8 + 4 + 4 + 8 + 24 + 32;
+    /// Length of the whitelists created before the stake durations, rate epochs and secondary
+    /// rates were added, which must be migrated with `migrate_whitelist_proof`.
+    pub const LEGACY_LEN: usize = 32 + 32 + 8 + 1;
     pub const PREFIX: &'static [u8] = b"collection_data";
 
+    pub fn new(
+        whitelisted_address: Pubkey,
+        farm: Pubkey,
+        reward_rate: u64,
+        secondary_reward_rate: u64,
+        ty: WhitelistType,
+        min_stake_duration: u64,
+        unstake_cooldown: u32,
+    ) -> Self {
+        Self {
+            whitelisted_address,
+            farm,
+            reward_rate,
+            ty,
+            min_stake_duration,
+            unstake_cooldown,
+            rate_epoch: 0,
+            secondary_reward_rate,
+            _reserved: [0; 24],
+            _reserved2: [0; 32],
+        }
+    }
+
+    pub fn update_reward_rate(
+        &mut self,
+        reward_rate: u64,
+        secondary_reward_rate: u64,
+    ) -> Result<()> {
+        self.reward_rate = reward_rate;
+        self.secondary_reward_rate = secondary_reward_rate;
+        self.rate_epoch = self
+            .rate_epoch
+            .checked_add(1)
+            .ok_or(StakingError::ArithmeticError)?;
+
+        Ok(())
+    }
+
+    /// Reward rate of `amount` staked tokens with the given buff factor.
+    pub fn reward_rate_for(&self, amount: u64, buff_factor: u64) -> Result<u64> {
+        Self::rate_for(self.reward_rate, amount, buff_factor)
+    }
+
+    /// Secondary reward rate of `amount` staked tokens with the given buff factor.
+    pub fn secondary_reward_rate_for(&self, amount: u64, buff_factor: u64) -> Result<u64> {
+        Self::rate_for(self.secondary_reward_rate, amount, buff_factor)
+    }
+
+    fn rate_for(rate: u64, amount: u64, buff_factor: u64) -> Result<u64> {
+        let base = amount
+            .checked_mul(rate)
+            .ok_or(StakingError::ArithmeticError)?;
+
+        calculate_reward_rate(base, buff_factor)
+    }
+
     pub fn validate<'info>(
         proof: &Account<Self>,
         mint: &Account<'info, Mint>,
         program_id: &Pubkey,
         remaining_accounts: &[AccountInfo<'info>],
+        merkle_proof: &[[u8; 32]],
     ) -> Result<()> {
         use WhitelistType::*;
         let creator_or_mint_key = match proof.ty {
             Mint => mint.key(),
-            Creator => {
-                // If we need to check the creator, then we look at the remaining accounts so we
-                // can access this mint's metadata account.
-                let raw_account = remaining_accounts.first();
-                let metadata = raw_account // Option<T>
-                    .ok_or_else(|| {
-                        msg!("Metadata account for mint {} was not given.", mint.key());
-                        ProgramError::InvalidAccountData
-                    })
-                    .map(|acc| validate_metadata_account(acc, &mint.to_account_info()))??;
-
-                metadata_creator(&metadata)?
+            Creator => metadata_creator(&mint_metadata(mint, remaining_accounts)?)?,
+            Collection => metadata_collection(&mint_metadata(mint, remaining_accounts)?)?,
+            MerkleRoot => {
+                let root = proof.whitelisted_address.to_bytes();
+
+                require!(
+                    merkle::verify(merkle_proof, &root, merkle::leaf(&mint.key())),
+                    StakingError::InvalidMerkleProof
+                );
+
+                proof.whitelisted_address
             }
         };
 
@@ -65,6 +142,21 @@ impl WhitelistProof {
     }
 }
 
+// If we need to check the creator or collection, then we look at the remaining accounts so we
+// can access this mint's metadata account.
+fn mint_metadata<'info>(
+    mint: &Account<'info, Mint>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<MetadataAccount> {
+    remaining_accounts
+        .first() // Option<T>
+        .ok_or_else(|| {
+            msg!("Metadata account for mint {} was not given.", mint.key());
+            ProgramError::InvalidAccountData
+        })
+        .map(|acc| validate_metadata_account(acc, &mint.to_account_info()))?
+}
+
 pub fn validate_metadata_account<'info>(
     metadata: &AccountInfo<'info>,
     mint: &AccountInfo<'info>,
@@ -83,3 +175,63 @@ pub fn validate_metadata_account<'info>(
 
     MetadataAccount::try_deserialize(&mut metadata.try_borrow_mut_data()?.as_ref())
 }
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Discriminator;
+
+    use super::*;
+
+    // The layout of the whitelists created before `min_stake_duration` was added.
+    #[derive(AnchorSerialize)]
+    struct LegacyWhitelistProof {
+        whitelisted_address: Pubkey,
+        farm: Pubkey,
+        reward_rate: u64,
+        ty: WhitelistType,
+    }
+
+    fn legacy_account(proof: &LegacyWhitelistProof) -> Vec<u8> {
+        let mut data = WhitelistProof::discriminator().to_vec();
+        proof.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn legacy_len_matches_the_legacy_layout() {
+        let proof = LegacyWhitelistProof {
+            whitelisted_address: Pubkey::new_unique(),
+            farm: Pubkey::new_unique(),
+            reward_rate: 100,
+            ty: WhitelistType::Creator,
+        };
+
+        assert_eq!(legacy_account(&proof).len(), 8 + WhitelistProof::LEGACY_LEN);
+    }
+
+    #[test]
+    fn legacy_accounts_load_once_migrated() {
+        let legacy = LegacyWhitelistProof {
+            whitelisted_address: Pubkey::new_unique(),
+            farm: Pubkey::new_unique(),
+            reward_rate: 100,
+            ty: WhitelistType::Mint,
+        };
+        let mut data = legacy_account(&legacy);
+
+        assert!(WhitelistProof::try_deserialize(&mut data.as_slice()).is_err());
+
+        // What `migrate_whitelist_proof` does to the account data.
+        data.resize(8 + WhitelistProof::LEN, 0);
+        let proof = WhitelistProof::try_deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(proof.whitelisted_address, legacy.whitelisted_address);
+        assert_eq!(proof.farm, legacy.farm);
+        assert_eq!(proof.reward_rate, 100);
+        assert_eq!(proof.ty, WhitelistType::Mint);
+        assert_eq!(proof.min_stake_duration, 0);
+        assert_eq!(proof.unstake_cooldown, 0);
+        assert_eq!(proof.rate_epoch, 0);
+        assert_eq!(proof.secondary_reward_rate, 0);
+    }
+}
@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-use anchor_lang::{prelude::*, system_program};
+use anchor_lang::{prelude::*, system_program, Discriminator};
 use anchor_spl::token::{self, TokenAccount};
 use solutils::wrappers::metadata::MetadataAccount;
 
@@ -58,6 +58,59 @@ pub fn close_ata<'info>(
     token::close_account(ctx)
 }
 
+/// Closes a program owned account without deserializing it.
+pub fn close_account<'info>(
+    account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = destination
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(StakingError::ArithmeticError)?;
+
+    **destination.try_borrow_mut_lamports()? = lamports;
+    **account.try_borrow_mut_lamports()? = 0;
+    account.try_borrow_mut_data()?.fill(0);
+
+    Ok(())
+}
+
+/// Grows an account written with the shorter, older layout of `T` to `len`, zero-filling the
+/// new fields and topping up its rent from `payer`.
+pub fn migrate_account<'info, T: Discriminator>(
+    account: AccountInfo<'info>,
+    legacy_len: usize,
+    len: usize,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    require!(
+        account.data_len() == 8 + legacy_len,
+        StakingError::AccountAlreadyMigrated
+    );
+    require!(
+        account.try_borrow_data()?[..8] == T::discriminator(),
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let rent = Rent::get()?
+        .minimum_balance(8 + len)
+        .saturating_sub(account.lamports());
+
+    if rent > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer,
+            to: account.clone(),
+        };
+
+        system_program::transfer(CpiContext::new(system_program, cpi_accounts), rent)?;
+    }
+
+    account.realloc(8 + len, true)?;
+
+    Ok(())
+}
+
 pub fn transfer_spl_ctx<'a, 'b, 'c, 'info>(
     from: AccountInfo<'info>,
     to: AccountInfo<'info>,
@@ -107,6 +160,20 @@ pub fn metadata_creator(metadata: &MetadataAccount) -> Result<Pubkey> {
         .ok_or(ProgramError::InvalidAccountData)?)
 }
 
+pub fn metadata_collection(metadata: &MetadataAccount) -> Result<Pubkey> {
+    Ok(metadata
+        .collection
+        .as_ref()
+        .and_then(|collection| {
+            if collection.verified {
+                Some(collection.key)
+            } else {
+                None
+            }
+        })
+        .ok_or(ProgramError::InvalidAccountData)?)
+}
+
 pub fn now_ts() -> Result<u64> {
     Clock::get()
         .map(|c| c.unix_timestamp as u64)
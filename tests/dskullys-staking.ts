@@ -15,7 +15,7 @@ import {
   TransactionInstruction,
 } from "@solana/web3.js";
 import { BN } from "bn.js";
-import { assert, expect } from "chai";
+import { expect } from "chai";
 
 import { StakingProgram } from "../app/lib";
 import {
@@ -24,7 +24,6 @@ import {
   Farmer,
   StakeReceipt,
 } from "../app/lib/gen/accounts";
-import { GemStillStaked } from "../app/lib/gen/errors/custom";
 import { WhitelistType } from "../app/lib/gen/types";
 import {
   findFarmAddress,
@@ -294,25 +293,65 @@ describe("staking-program", () => {
     expect(totalRewardRate.toNumber()).to.eql(expectedRewardRate);
   });
 
-  it("should not be able to stake more while still staking", async () => {
+  it("should be able to add to a fungible stake", async () => {
     const farm = findFarmAddress({
       authority: farmAuthority.publicKey,
       rewardMint,
     });
 
-    try {
-      const { ix } = await stakingClient.createStakeInstruction({
-        farm,
-        mint: rewardMint,
-        owner: userWallet.publicKey,
-        amount: new BN(5e8),
-      });
+    const farmer = findFarmerAddress({ farm, owner: userWallet.publicKey });
+    const stakeReceipt = findStakeReceiptAddress({ farmer, mint: rewardMint });
+
+    // Stake 0.5 more tokens
+    const { ix } = await stakingClient.createStakeInstruction({
+      farm,
+      mint: rewardMint,
+      owner: userWallet.publicKey,
+      amount: new BN(5e8),
+    });
+
+    await send(connection, [ix], [userWallet]);
+
+    const { totalRewardRate } = await Farmer.fetch(connection, farmer);
+    const { amount, rewardRate } = await StakeReceipt.fetch(
+      connection,
+      stakeReceipt
+    );
+
+    expect(amount.toNumber()).to.equal(1e9);
+    expect(rewardRate.toNumber()).to.equal(1e9);
+    expect(totalRewardRate.toNumber()).to.equal(1e9);
+  });
+
+  it("should be able to partially unstake a fungible token", async () => {
+    const farm = findFarmAddress({
+      authority: farmAuthority.publicKey,
+      rewardMint,
+    });
+
+    const farmer = findFarmerAddress({ farm, owner: userWallet.publicKey });
+    const stakeReceipt = findStakeReceiptAddress({ farmer, mint: rewardMint });
+
+    // Unstake 0.25 tokens
+    const { ix } = await stakingClient.createPartialUnstakeInstruction({
+      farm,
+      mint: rewardMint,
+      owner: userWallet.publicKey,
+      amount: new BN(2.5e8),
+    });
+
+    await send(connection, [ix], [userWallet]);
+
+    const { totalRewardRate } = await Farmer.fetch(connection, farmer);
+    const { amount, rewardRate, endTs } = await StakeReceipt.fetch(
+      connection,
+      stakeReceipt
+    );
 
-      await send(connection, [ix], [userWallet]);
-      assert(false);
-    } catch (e) {
-      expect(e).to.be.instanceOf(GemStillStaked);
-    }
+    expect(amount.toNumber()).to.equal(7.5e8);
+    expect(rewardRate.toNumber()).to.equal(7.5e8);
+    expect(totalRewardRate.toNumber()).to.equal(7.5e8);
+    expect(endTs).to.be.null;
   });
 
   it("should be able to remove and address from the whitelist", async () => {